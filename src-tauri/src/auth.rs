@@ -0,0 +1,303 @@
+//! Interactive credential and passphrase prompts for push/pull/fetch.
+//!
+//! `run_git_command` hard-codes `GIT_TERMINAL_PROMPT=0` and batch-mode SSH,
+//! so anything needing a password or an encrypted key's passphrase just
+//! fails. The commands here instead point git at a tiny askpass helper
+//! (this same binary, invoked with `--celerix-askpass`) that forwards each
+//! prompt over a loopback socket back into the running app, which emits a
+//! `credential-prompt` event and awaits the frontend's answer before
+//! replying to git - so the operation that triggered the prompt blocks
+//! until the user answers, then continues transparently.
+//!
+//! `GIT_ASKPASS`/`core.askPass` go through git's own command parser, which
+//! splits the configured string on whitespace before exec'ing it - so
+//! `"<exe> --celerix-askpass"` works there. `SSH_ASKPASS` does not: OpenSSH
+//! execs its value as a single literal program path with no splitting, so
+//! any arguments baked into the string just make it look for a program that
+//! doesn't exist. `SSH_ASKPASS` is therefore always set to the bare
+//! executable path, and the callback port is passed via `ASKPASS_PORT_ENV`
+//! instead of an argv word.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use crate::git::SshKeyInfo;
+
+/// Argv marker that re-invokes this same binary as an askpass helper
+/// instead of launching the GUI. Checked at the very top of `main`.
+pub const ASKPASS_FLAG: &str = "--celerix-askpass";
+
+/// Env var carrying the loopback callback port to the askpass helper.
+/// Needed because `SSH_ASKPASS` can only name a bare executable (see the
+/// module doc comment) - there's no argv slot to put the port in when
+/// `ssh`/`ssh-add` invoke it directly.
+pub(crate) const ASKPASS_PORT_ENV: &str = "CELERIX_ASKPASS_PORT";
+
+/// True if this process was re-invoked as the askpass helper, whether via
+/// `ASKPASS_FLAG` (the `GIT_ASKPASS` path, which keeps the flag in argv) or
+/// `ASKPASS_PORT_ENV` alone (the `SSH_ASKPASS` path, which can't).
+pub fn is_askpass_invocation() -> bool {
+    std::env::args().any(|a| a == ASKPASS_FLAG) || std::env::var(ASKPASS_PORT_ENV).is_ok()
+}
+
+/// Payload for the `credential-prompt` event.
+#[derive(Serialize, Clone)]
+struct CredentialPrompt {
+    request_id: String,
+    /// The literal prompt text git/ssh sent (e.g. `"Password for
+    /// 'https://user@host':"` or `"Enter passphrase for key '...':"`).
+    prompt: String,
+}
+
+/// Pending askpass prompts awaiting a frontend answer, keyed by request id.
+#[derive(Default)]
+pub struct PendingPrompts(Mutex<HashMap<String, oneshot::Sender<String>>>);
+
+/// If invoked as the askpass helper (see `is_askpass_invocation`), handles
+/// the askpass round-trip and exits the process instead of returning -
+/// called at the very top of `main` before the Tauri app is built, since
+/// git/ssh spawn this as a short-lived child process.
+///
+/// The callback port always arrives via `ASKPASS_PORT_ENV`; the prompt text
+/// is whatever argument git/ssh appended last (`<cmd> --celerix-askpass
+/// <prompt>` for `GIT_ASKPASS`, `<exe> <prompt>` for `SSH_ASKPASS`).
+pub fn run_as_askpass_if_requested() -> ! {
+    let port: u16 = std::env::var(ASKPASS_PORT_ENV)
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .expect("askpass helper invoked without a callback port");
+    let prompt = std::env::args().last().unwrap_or_default();
+
+    let answer = tauri::async_runtime::block_on(async move {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).await.expect("connect to celerix askpass callback");
+        stream.write_all(prompt.as_bytes()).await.ok();
+        stream.write_all(b"\n").await.ok();
+        stream.shutdown().await.ok();
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).await.ok();
+        line.trim_end().to_string()
+    });
+
+    print!("{}", answer);
+    std::process::exit(0);
+}
+
+/// Starts a loopback listener that forwards every prompt it receives into a
+/// `credential-prompt` event and replies with the frontend's answer, one
+/// connection at a time, until the returned stop handle is dropped.
+///
+/// A single interactive git/ssh operation often needs more than one
+/// round-trip - an HTTPS username followed by a password, or a passphrase
+/// retry after a typo - so this keeps accepting connections in a loop
+/// rather than exiting after the first one. The caller is expected to hold
+/// the returned `oneshot::Sender` alive for as long as the git/ssh child
+/// might still connect back, and let it drop once that child has exited,
+/// which stops the accept loop.
+async fn spawn_askpass_listener(app: AppHandle) -> std::io::Result<(u16, oneshot::Sender<()>)> {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let port = listener.local_addr()?.port();
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(_) => break,
+                },
+                _ = &mut stop_rx => break,
+            };
+
+            let mut reader = BufReader::new(stream);
+            let mut prompt = String::new();
+            if reader.read_line(&mut prompt).await.is_err() {
+                continue;
+            }
+            let prompt = prompt.trim_end().to_string();
+
+            let request_id = uuid::Uuid::new_v4().to_string();
+            let (tx, rx) = oneshot::channel();
+            {
+                let state = app.state::<PendingPrompts>();
+                if let Ok(mut pending) = state.0.lock() {
+                    pending.insert(request_id.clone(), tx);
+                }
+            }
+            let _ = app.emit("credential-prompt", CredentialPrompt { request_id, prompt });
+
+            let answer = rx.await.unwrap_or_default();
+            let mut stream = reader.into_inner();
+            let _ = stream.write_all(answer.as_bytes()).await;
+            let _ = stream.write_all(b"\n").await;
+        }
+    });
+
+    Ok((port, stop_tx))
+}
+
+/// Feeds the frontend's answer to a `credential-prompt` back to the
+/// waiting askpass helper.
+///
+/// # Errors
+/// Returns an error if no prompt is waiting on that request id.
+#[tauri::command]
+pub fn answer_credential_prompt(app: AppHandle, request_id: String, answer: String) -> Result<(), String> {
+    let state = app.state::<PendingPrompts>();
+    let mut pending = state.0.lock().map_err(|_| "Prompt state poisoned")?;
+    let sender = pending.remove(&request_id).ok_or_else(|| format!("No pending prompt '{}'", request_id))?;
+    sender.send(answer).map_err(|_| "Prompt was abandoned".to_string())
+}
+
+/// Single-quotes `value` for safe interpolation into `GIT_SSH_COMMAND`,
+/// which git hands to a shell rather than exec'ing directly - so a key
+/// path containing a space (a common enough `~/.ssh/...` location on
+/// Windows, e.g. `C:\Users\John Smith\...`) would otherwise split into the
+/// wrong argv and silently fail to select the key.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Runs a git command with interactive askpass support: `GIT_ASKPASS`
+/// points at this binary, and `GIT_SSH_COMMAND` is set to use `ssh_key` if
+/// one was selected for this repo (see `SshKeyInfo`).
+pub async fn run_git_command_interactive(
+    app: &AppHandle,
+    path: &str,
+    args: &[&str],
+    ssh_key: Option<&SshKeyInfo>,
+) -> Result<std::process::Output, String> {
+    let (port, _stop_listener) = spawn_askpass_listener(app.clone()).await.map_err(|e| e.to_string())?;
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let exe_str = exe.to_string_lossy().to_string();
+    // `GIT_ASKPASS` goes through git's own parser, so the flag can ride
+    // along in the command string; `SSH_ASKPASS` is exec'd literally by
+    // ssh and must be a bare path (see the module doc comment).
+    let git_askpass_cmd = format!("{} {}", exe_str, ASKPASS_FLAG);
+
+    let mut command = tokio::process::Command::new("git");
+    command.arg("-C").arg(path).args(args);
+    command.env(ASKPASS_PORT_ENV, port.to_string());
+    command.env("GIT_ASKPASS", &git_askpass_cmd);
+    command.env("SSH_ASKPASS", &exe_str);
+    command.env("SSH_ASKPASS_REQUIRE", "force");
+
+    let ssh_command = match ssh_key {
+        Some(key) => format!("ssh -i {} -o IdentitiesOnly=yes", shell_quote(&key.path)),
+        None => "ssh".to_string(),
+    };
+    command.env("GIT_SSH_COMMAND", ssh_command);
+
+    command.output().await.map_err(|e| format!("Failed to execute git {}: {}", args.first().unwrap_or(&"command"), e))
+}
+
+/// Like `git::git_fetch`, but prompts interactively for credentials or an
+/// SSH key passphrase instead of failing outright.
+///
+/// # Errors
+/// Returns an error if the fetch fails, including when a prompt is left
+/// unanswered or answered wrongly.
+#[tauri::command]
+pub async fn git_fetch_interactive(app: AppHandle, path: String, ssh_key: Option<SshKeyInfo>) -> Result<(), crate::error::GitError> {
+    let output = run_git_command_interactive(&app, &path, &["fetch", "--all"], ssh_key.as_ref()).await?;
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
+    Ok(())
+}
+
+/// Like `git::git_pull`, but prompts interactively for credentials or an
+/// SSH key passphrase instead of failing outright.
+///
+/// # Errors
+/// Returns an error if the pull fails, including when a prompt is left
+/// unanswered or answered wrongly.
+#[tauri::command]
+pub async fn git_pull_interactive(app: AppHandle, path: String, ssh_key: Option<SshKeyInfo>) -> Result<(), crate::error::GitError> {
+    let output = run_git_command_interactive(&app, &path, &["pull"], ssh_key.as_ref()).await?;
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
+    Ok(())
+}
+
+/// Like `git::git_push`, but prompts interactively for credentials or an
+/// SSH key passphrase instead of failing outright.
+///
+/// # Errors
+/// Returns an error if the push fails, including when a prompt is left
+/// unanswered or answered wrongly.
+#[tauri::command]
+pub async fn git_push_interactive(app: AppHandle, path: String, ssh_key: Option<SshKeyInfo>) -> Result<(), crate::error::GitError> {
+    let output = run_git_command_interactive(&app, &path, &["push"], ssh_key.as_ref()).await?;
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
+    Ok(())
+}
+
+/// Starts a loopback listener like `spawn_askpass_listener`, but answers
+/// every prompt it receives with `answer` directly instead of forwarding it
+/// to the frontend - used when the caller already has the passphrase in
+/// hand. Like `spawn_askpass_listener`, it keeps accepting connections
+/// (some callers, e.g. `ssh-keygen` confirming a new passphrase, need more
+/// than one round-trip) until the returned stop handle is dropped.
+pub(crate) async fn spawn_fixed_answer_listener(answer: String) -> std::io::Result<(u16, oneshot::Sender<()>)> {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let port = listener.local_addr()?.port();
+    let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(_) => break,
+                },
+                _ = &mut stop_rx => break,
+            };
+
+            let mut reader = BufReader::new(stream);
+            let mut prompt = String::new();
+            if reader.read_line(&mut prompt).await.is_err() {
+                continue;
+            }
+            let mut stream = reader.into_inner();
+            let _ = stream.write_all(answer.as_bytes()).await;
+            let _ = stream.write_all(b"\n").await;
+        }
+    });
+
+    Ok((port, stop_tx))
+}
+
+/// Adds a key to `ssh-agent` so the fetch/pull/push commands can use it
+/// without prompting each time.
+///
+/// If `passphrase` is given it answers the `ssh-add` passphrase prompt
+/// directly; otherwise it reuses the same askpass round-trip as
+/// `run_git_command_interactive` to ask the frontend.
+///
+/// # Errors
+/// Returns an error if `ssh-add` fails, e.g. the passphrase is wrong or no
+/// agent is running.
+#[tauri::command]
+pub async fn add_key_to_agent(app: AppHandle, path_to_key: String, passphrase: Option<String>) -> Result<(), String> {
+    let (port, _stop_listener) = match passphrase {
+        Some(passphrase) => spawn_fixed_answer_listener(passphrase).await.map_err(|e| e.to_string())?,
+        None => spawn_askpass_listener(app.clone()).await.map_err(|e| e.to_string())?,
+    };
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+
+    let output = tokio::process::Command::new("ssh-add")
+        .arg(&path_to_key)
+        .env(ASKPASS_PORT_ENV, port.to_string())
+        .env("SSH_ASKPASS", exe.to_string_lossy().to_string())
+        .env("SSH_ASKPASS_REQUIRE", "force")
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!("ssh-add failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
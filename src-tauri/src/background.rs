@@ -0,0 +1,79 @@
+//! Background-run support: start-on-login and "minimize to tray".
+//!
+//! By default closing the main window quits the app. Users running several
+//! repos under the file watcher want Celerix to keep running in the
+//! background instead, so this adds a `close_to_tray` setting that
+//! intercepts the window's close request, plus login-item registration via
+//! `auto-launch` so the app can relaunch itself after a reboot.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use auto_launch::AutoLaunch;
+use tauri::{AppHandle, Manager};
+
+/// Whether closing the main window should hide it to the tray instead of
+/// quitting the app. Defaults to on, since that's the point of having a
+/// tray icon at all.
+static CLOSE_TO_TRAY: AtomicBool = AtomicBool::new(true);
+
+/// Returns whether the main window is currently configured to hide to tray
+/// on close, for use by the window close handler installed in `run()`.
+pub fn close_to_tray_enabled() -> bool {
+    CLOSE_TO_TRAY.load(Ordering::SeqCst)
+}
+
+/// Builds the `auto-launch` handle for the current executable.
+fn auto_launch() -> Result<AutoLaunch, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+    let exe_path = exe.to_string_lossy().to_string();
+    Ok(AutoLaunch::new("Celerix", &exe_path, &[] as &[&str]))
+}
+
+/// Enables or disables "hide to tray" on the main window's close button.
+///
+/// # Errors
+/// Never fails; returns `Result` for API symmetry with the other settings
+/// commands.
+#[tauri::command]
+pub fn set_close_to_tray(enabled: bool) -> Result<(), String> {
+    CLOSE_TO_TRAY.store(enabled, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Returns whether "hide to tray" is currently enabled.
+#[tauri::command]
+pub fn get_close_to_tray() -> bool {
+    close_to_tray_enabled()
+}
+
+/// Registers or unregisters Celerix as a login item on the current OS.
+///
+/// # Errors
+/// Returns an error if the platform's autostart mechanism rejects the
+/// registration (e.g. missing permissions).
+#[tauri::command]
+pub fn set_launch_at_login(enabled: bool) -> Result<(), String> {
+    let launcher = auto_launch()?;
+    if enabled {
+        launcher.enable().map_err(|e| e.to_string())
+    } else {
+        launcher.disable().map_err(|e| e.to_string())
+    }
+}
+
+/// Returns whether Celerix is currently registered as a login item.
+///
+/// # Errors
+/// Returns an error if the platform's autostart mechanism cannot be
+/// queried.
+#[tauri::command]
+pub fn get_launch_at_login() -> Result<bool, String> {
+    auto_launch()?.is_enabled().map_err(|e| e.to_string())
+}
+
+/// Updates the tray's Show/Hide item label to reflect the main window's
+/// current visibility.
+pub fn sync_tray_visibility_label(app: &AppHandle, show_hide_item: &tauri::menu::MenuItem<tauri::Wry>) {
+    let Some(window) = app.get_webview_window("main") else { return };
+    let is_visible = window.is_visible().unwrap_or(true);
+    let _ = show_hide_item.set_text(if is_visible { "Hide" } else { "Show" });
+}
@@ -0,0 +1,180 @@
+//! Bulk cleanup of stale local branches.
+//!
+//! Classifies every local branch against one or more base branches so the
+//! frontend can offer a "clean up merged branches" sweep instead of making
+//! the user delete them one at a time. A branch is merged either by plain
+//! ancestry (fast-forward or merge commit) or by squash - where the branch
+//! tip is never reachable from the base but every one of its commits'
+//! changes already landed there, which `git cherry` reports by prefixing
+//! each commit `-` instead of `+`.
+
+use serde::Serialize;
+
+use crate::git::{get_git_remotes, run_git_command, RemoteBranchName};
+
+/// How a local branch relates to the base branches it was checked against.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum BranchDisposition {
+    /// Reachable from a base tip - a plain merge or fast-forward.
+    MergedLocal,
+    /// Not reachable, but every commit's changes already landed on a base
+    /// (detected via `git cherry`) - a squash merge.
+    MergedSquash,
+    /// Has a configured upstream whose remote-tracking ref no longer
+    /// exists (`git for-each-ref`'s `%(upstream:track)` shows `[gone]`).
+    Gone,
+    /// None of the above - neither merged nor known to be abandoned.
+    Stray,
+}
+
+/// A local branch tagged with its cleanup disposition.
+#[derive(Serialize)]
+pub struct StaleBranch {
+    pub name: String,
+    pub disposition: BranchDisposition,
+}
+
+/// Returns the short name of the currently checked-out branch, if any.
+async fn current_branch_name(path: &str) -> Result<Option<String>, String> {
+    let repo = gix::open(path).or_else(|_| gix::discover(path)).map_err(|e| e.to_string())?;
+    let head_ref = repo.head().map_err(|e| e.to_string())?;
+    Ok(head_ref.referent_name().map(|n| {
+        let full_name = n.as_bstr().to_string();
+        full_name.strip_prefix("refs/heads/").map(str::to_string).unwrap_or(full_name)
+    }))
+}
+
+/// Resolves the default base branch when `base_branches` is empty: the
+/// upstream of whichever of `main`/`master` exists locally.
+async fn default_base_branch(path: &str) -> Result<String, String> {
+    for candidate in ["main", "master"] {
+        let verify = run_git_command(path, &["rev-parse", "--verify", "--quiet", candidate]).await?;
+        if !verify.status.success() { continue; }
+        let upstream = run_git_command(path, &["rev-parse", "--abbrev-ref", &format!("{}@{{upstream}}", candidate)]).await?;
+        if upstream.status.success() {
+            return Ok(String::from_utf8_lossy(&upstream.stdout).trim().to_string());
+        }
+        return Ok(candidate.to_string());
+    }
+    Err("Could not find a 'main' or 'master' branch to use as the base".to_string())
+}
+
+/// Returns `true` if `branch`'s tip is an ancestor of `base` (a plain
+/// merge or fast-forward landed it).
+async fn is_ancestor(path: &str, branch: &str, base: &str) -> Result<bool, String> {
+    let output = run_git_command(path, &["merge-base", "--is-ancestor", branch, base]).await?;
+    Ok(output.status.success())
+}
+
+/// Returns `true` if every commit unique to `branch` (relative to `base`)
+/// is reported by `git cherry` as already applied (`-` prefix) - a squash
+/// merge, where no commit in `branch` is literally reachable from `base`.
+async fn is_squash_merged(path: &str, branch: &str, base: &str) -> Result<bool, String> {
+    let output = run_git_command(path, &["cherry", base, branch]).await?;
+    if !output.status.success() { return Ok(false); }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().filter(|l| !l.trim().is_empty()).collect();
+    Ok(!lines.is_empty() && lines.iter().all(|l| l.starts_with('-')))
+}
+
+/// Classifies every local branch (other than the current one and
+/// `base_branches`) as merged, gone, or stray relative to `base_branches`.
+///
+/// # Errors
+/// Returns an error if the repository cannot be opened, no base branch
+/// can be resolved, or the underlying `git` commands fail.
+#[tauri::command]
+pub async fn git_classify_stale_branches(path: String, base_branches: Vec<String>) -> Result<Vec<StaleBranch>, String> {
+    let bases = if base_branches.is_empty() { vec![default_base_branch(&path).await?] } else { base_branches };
+    let current = current_branch_name(&path).await?;
+
+    let output = run_git_command(&path, &["for-each-ref", "--format=%(refname:short) %(upstream) %(upstream:track)", "refs/heads/"]).await?;
+    if !output.status.success() {
+        return Err(format!("Git for-each-ref failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let mut results = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut parts = line.splitn(3, ' ');
+        let name = match parts.next() { Some(n) if !n.is_empty() => n.to_string(), _ => continue };
+        let upstream = parts.next().unwrap_or("").trim();
+        let track = parts.next().unwrap_or("").trim();
+
+        if Some(&name) == current.as_ref() || bases.contains(&name) {
+            continue;
+        }
+
+        if !upstream.is_empty() && track.contains("[gone]") {
+            results.push(StaleBranch { name, disposition: BranchDisposition::Gone });
+            continue;
+        }
+
+        let mut disposition = BranchDisposition::Stray;
+        for base in &bases {
+            if is_ancestor(&path, &name, base).await? {
+                disposition = BranchDisposition::MergedLocal;
+                break;
+            }
+            if is_squash_merged(&path, &name, base).await? {
+                disposition = BranchDisposition::MergedSquash;
+                break;
+            }
+        }
+        results.push(StaleBranch { name, disposition });
+    }
+
+    Ok(results)
+}
+
+/// Returns the `(remote, remote-branch)` pair for `branch`'s upstream, or
+/// `None` if it has no upstream configured.
+async fn upstream_for(path: &str, branch: &str) -> Result<Option<(String, String)>, String> {
+    let output = run_git_command(path, &["rev-parse", "--abbrev-ref", &format!("{}@{{upstream}}", branch)]).await?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let upstream = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // Match against the repo's actual configured remotes rather than
+    // splitting on the first `/`, which breaks for remotes or branches
+    // containing a slash (see `RemoteBranchName::parse`).
+    let remotes = get_git_remotes(path.to_string())?;
+    Ok(RemoteBranchName::parse(&upstream, &remotes).map(|rb| (rb.remote, rb.branch)))
+}
+
+/// Deletes each of `names` with `git branch -D`, silently skipping the
+/// currently checked-out branch. If `delete_remote` is set, also deletes
+/// each branch's upstream ref on its remote (`git push <remote> --delete
+/// <branch>`) before removing the local branch.
+///
+/// # Errors
+/// Returns an error listing every branch (local or remote) that failed to
+/// delete; deletions that succeeded before a failure are still applied.
+#[tauri::command]
+pub async fn git_trim_branches(path: String, names: Vec<String>, delete_remote: bool) -> Result<Vec<String>, String> {
+    let current = current_branch_name(&path).await?;
+    let mut deleted = Vec::new();
+    let mut errors = Vec::new();
+    for name in names {
+        if Some(&name) == current.as_ref() { continue; }
+
+        if delete_remote {
+            if let Some((remote, remote_branch)) = upstream_for(&path, &name).await? {
+                let output = run_git_command(&path, &["push", &remote, "--delete", &remote_branch]).await?;
+                if !output.status.success() {
+                    errors.push(format!("{} (remote): {}", name, String::from_utf8_lossy(&output.stderr).trim()));
+                }
+            }
+        }
+
+        let output = run_git_command(&path, &["branch", "-D", &name]).await?;
+        if output.status.success() {
+            deleted.push(name);
+        } else {
+            errors.push(format!("{}: {}", name, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+    Ok(deleted)
+}
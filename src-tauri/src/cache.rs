@@ -0,0 +1,85 @@
+//! A short-lived cache for read-only git queries.
+//!
+//! `get_git_commits`, `get_commit_files`, and `get_commit_file_diff`
+//! re-shell out to git on every frontend call, which gets expensive for
+//! large histories re-rendered on every scroll tick. This caches their
+//! JSON-encoded results behind a `(repo_path, command, args)` key with a
+//! short time-to-live, and drops a repo's entries whenever a mutating
+//! command runs against it or the repo's `HEAD` moves.
+
+use std::sync::Mutex;
+use std::time::Duration;
+use moka::future::Cache;
+
+/// How long a cached entry stays valid without being invalidated.
+const TTL: Duration = Duration::from_secs(10);
+
+/// Bounds the cache's memory use across all open repos.
+const CAPACITY: u64 = 256;
+
+/// Shared cache state, managed by Tauri. Values are pre-serialized JSON so
+/// the cache doesn't need to be generic over every command's return type.
+pub struct GitQueryCache {
+    entries: Cache<String, String>,
+    /// The last-known `HEAD` oid per repo, used to detect that a commit
+    /// landed without going through one of our own mutating commands
+    /// (e.g. a commit made outside the app).
+    head_oids: Mutex<std::collections::HashMap<String, String>>,
+}
+
+impl Default for GitQueryCache {
+    fn default() -> Self {
+        Self {
+            entries: Cache::builder().time_to_live(TTL).max_capacity(CAPACITY).support_invalidation_closures().build(),
+            head_oids: Mutex::new(std::collections::HashMap::new()),
+        }
+    }
+}
+
+/// Builds the cache key for a given repo/command/args triple.
+fn cache_key(repo_path: &str, command: &str, args: &str) -> String {
+    format!("{}\u{1}{}\u{1}{}", repo_path, command, args)
+}
+
+/// Reads the current `HEAD` oid for `repo_path`, if the repo can be opened.
+fn current_head_oid(repo_path: &str) -> Option<String> {
+    let repo = gix::open(repo_path).or_else(|_| gix::discover(repo_path)).ok()?;
+    let head = repo.head_id().ok()?;
+    Some(head.to_string())
+}
+
+impl GitQueryCache {
+    /// Returns the cached value for this key if present and `HEAD` hasn't
+    /// moved since it was stored, otherwise `None`.
+    pub async fn get(&self, repo_path: &str, command: &str, args: &str) -> Option<String> {
+        if let Some(stored_head) = self.head_oids.lock().ok()?.get(repo_path).cloned() {
+            if current_head_oid(repo_path) != Some(stored_head) {
+                self.invalidate_repo(repo_path).await;
+                return None;
+            }
+        }
+        self.entries.get(&cache_key(repo_path, command, args)).await
+    }
+
+    /// Stores `value` for this key, recording the repo's current `HEAD` so
+    /// a later `HEAD` mismatch invalidates it.
+    pub async fn put(&self, repo_path: &str, command: &str, args: &str, value: String) {
+        if let Some(oid) = current_head_oid(repo_path) {
+            if let Ok(mut oids) = self.head_oids.lock() {
+                oids.insert(repo_path.to_string(), oid);
+            }
+        }
+        self.entries.insert(cache_key(repo_path, command, args), value).await;
+    }
+
+    /// Drops every cached entry for `repo_path`. Called after the commit and
+    /// stash commands so the next read reflects them immediately rather
+    /// than waiting out the TTL. The stage/unstage commands do not call it.
+    pub async fn invalidate_repo(&self, repo_path: &str) {
+        let prefix = format!("{}\u{1}", repo_path);
+        self.entries.invalidate_entries_if(move |key, _| key.starts_with(&prefix)).ok();
+        if let Ok(mut oids) = self.head_oids.lock() {
+            oids.remove(repo_path);
+        }
+    }
+}
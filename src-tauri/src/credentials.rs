@@ -0,0 +1,119 @@
+//! Credential support for HTTPS remotes.
+//!
+//! SSH auth is already covered by `get_ssh_key_info`/`generate_ssh_key`, but
+//! there was no story for token-authenticated HTTPS remotes (GitHub/GitLab
+//! PATs). This stores secrets in the OS keychain via `keyring` and makes
+//! them available to `git` itself: `run_git_command` (see `git.rs`)
+//! configures `credential.helper` to re-invoke this binary with
+//! `CREDENTIAL_HELPER_FLAG`, so any HTTPS transfer git runs - fetch, pull,
+//! push, clone - transparently fills in a stored token the same way a
+//! system credential helper would.
+//!
+//! Only pre-stored tokens are supported: `run_as_credential_helper_if_requested`
+//! runs as a short-lived re-exec of this binary, spawned directly by `git`
+//! before `tauri::Builder` ever runs, so it has no `AppHandle` and no way to
+//! reach the running app to prompt the user. If nothing is stored for a
+//! remote, the `get` action simply prints nothing and git falls back to
+//! failing the transfer. The interactive fetch/pull/push commands in
+//! `auth.rs` do not configure this helper, so they never use a stored
+//! token; they ask for credentials through the askpass prompt instead.
+//! Add credentials for a remote via `store_remote_credentials` before
+//! fetching/pushing to it.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use serde::{Deserialize, Serialize};
+
+/// The keyring "service" namespace Celerix stores remote credentials under.
+const SERVICE: &str = "celerix-git-gui";
+
+/// Argv marker that re-invokes this same binary as a `credential.helper`
+/// instead of launching the GUI. Checked at the very top of `main`.
+pub const CREDENTIAL_HELPER_FLAG: &str = "--celerix-credential-helper";
+
+/// True if this process was re-invoked as the `git credential` helper.
+pub fn is_credential_helper_invocation() -> bool {
+    std::env::args().any(|a| a == CREDENTIAL_HELPER_FLAG)
+}
+
+/// If invoked with `CREDENTIAL_HELPER_FLAG`, implements git's credential
+/// helper protocol against the OS keychain and exits instead of returning -
+/// called at the very top of `main`, since git spawns this as a
+/// short-lived child process whenever an HTTPS transfer needs auth.
+///
+/// Only the `get` action does anything: it looks up `lookup_stored` keyed
+/// by the exact `protocol://host/path` URL git is requesting credentials
+/// for (`credential.useHttpPath` is enabled alongside this helper so `path`
+/// is populated) and prints `username=`/`password=` if found. `store` and
+/// `erase` are no-ops - secrets are only ever written via
+/// `store_remote_credentials`/`forget_remote_credentials` from the
+/// settings UI, since `GIT_TERMINAL_PROMPT=0` means git never collects a
+/// credential of its own to store.
+pub fn run_as_credential_helper_if_requested() -> ! {
+    let action = std::env::args().nth(2).unwrap_or_default();
+
+    let mut fields = HashMap::new();
+    for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            fields.insert(key.to_string(), value.to_string());
+        }
+    }
+
+    if action == "get" {
+        let protocol = fields.get("protocol").cloned().unwrap_or_default();
+        let host = fields.get("host").cloned().unwrap_or_default();
+        let path = fields.get("path").cloned().unwrap_or_default();
+        let remote = format!("{}://{}/{}", protocol, host, path);
+        if let Some(creds) = lookup_stored(&remote) {
+            println!("username={}", creds.username);
+            println!("password={}", creds.secret);
+        }
+    }
+
+    std::process::exit(0);
+}
+
+/// Credentials for a single remote, as entered by the user.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RemoteCredentials {
+    /// Username, or the literal token for token-only hosts like GitHub PATs.
+    pub username: String,
+    /// Password or personal access token.
+    pub secret: String,
+}
+
+/// Saves credentials for `remote` in the OS keychain (Windows Credential
+/// Manager / macOS Keychain / libsecret, depending on platform).
+///
+/// # Errors
+/// Returns an error if the OS keychain rejects the write.
+#[tauri::command]
+pub fn store_remote_credentials(remote: String, username: String, secret: String) -> Result<(), String> {
+    let entry = keyring::Entry::new(SERVICE, &remote).map_err(|e| e.to_string())?;
+    let payload = serde_json::to_string(&RemoteCredentials { username, secret }).map_err(|e| e.to_string())?;
+    entry.set_password(&payload).map_err(|e| e.to_string())
+}
+
+/// Removes any stored credentials for `remote`.
+///
+/// # Errors
+/// Returns an error if the OS keychain rejects the deletion (missing
+/// entries are treated as success).
+#[tauri::command]
+pub fn forget_remote_credentials(remote: String) -> Result<(), String> {
+    let entry = keyring::Entry::new(SERVICE, &remote).map_err(|e| e.to_string())?;
+    match entry.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Looks up stored credentials for `remote`, consulting the OS keychain.
+pub(crate) fn lookup_stored(remote: &str) -> Option<RemoteCredentials> {
+    let entry = keyring::Entry::new(SERVICE, remote).ok()?;
+    let payload = entry.get_password().ok()?;
+    serde_json::from_str(&payload).ok()
+}
@@ -0,0 +1,166 @@
+//! Structured, syntax-highlighted diffs.
+//!
+//! `git::get_git_diff` / `get_commit_file_diff` return a raw unified diff
+//! that the frontend has to parse and color itself. This parses that same
+//! text into a typed `Diff`, then runs each line's content through
+//! `syntect` to produce CSS-class spans (`ClassStyle::Spaced`) instead of
+//! inline colors, so the frontend can render proper code coloring without
+//! reimplementing a highlighter in JS.
+
+use std::sync::OnceLock;
+use serde::Serialize;
+use syntect::easy::ClassedHTMLGenerator;
+use syntect::html::ClassStyle;
+use syntect::parsing::SyntaxSet;
+
+/// Whether a diff line is unchanged context, an addition, or a removal.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+/// A single line within a hunk, with its original/new line numbers and
+/// syntax-highlighted HTML for its content.
+#[derive(Serialize, Clone)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub old_lineno: Option<usize>,
+    pub new_lineno: Option<usize>,
+    pub html: String,
+}
+
+/// A single `@@ -a,b +c,d @@` hunk and its body lines.
+#[derive(Serialize, Clone)]
+pub struct Hunk {
+    pub old_start: usize,
+    pub old_lines: usize,
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub header: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// A parsed, highlighted diff for one file.
+#[derive(Serialize, Clone)]
+pub struct Diff {
+    pub hunks: Vec<Hunk>,
+}
+
+/// The loaded syntax definitions, built once and shared across calls.
+fn syntax_set() -> &'static SyntaxSet {
+    static SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+/// Parses an `@@ -a,b +c,d @@` header into its four numbers, defaulting
+/// line counts to 1 when git omits them (single-line ranges).
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let inner = line.strip_prefix("@@ ")?;
+    let end = inner.find(" @@")?;
+    let ranges = &inner[..end];
+    let mut parts = ranges.split_whitespace();
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let parse_range = |r: &str| -> Option<(usize, usize)> {
+        if let Some((start, len)) = r.split_once(',') {
+            Some((start.parse().ok()?, len.parse().ok()?))
+        } else {
+            Some((r.parse().ok()?, 1))
+        }
+    };
+    let (old_start, old_lines) = parse_range(old)?;
+    let (new_start, new_lines) = parse_range(new)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// Parses a unified diff for a single file and highlights each line's
+/// content using the syntax inferred from `file_path`'s extension.
+pub fn parse_and_highlight(unified_diff: &str, file_path: &str) -> Diff {
+    let set = syntax_set();
+    let syntax = set
+        .find_syntax_for_file(file_path)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| set.find_syntax_plain_text());
+
+    // Untracked-file diffs from `get_git_diff`'s synthetic fallback are just
+    // `--- /dev/null` / `+++ b/<f>` followed directly by `+` lines, with no
+    // `@@` header at all. Detect that up front so the loop below can
+    // synthesize the hunk git itself would have emitted for a new file,
+    // instead of silently dropping every line.
+    let has_hunk_header = unified_diff.lines().any(|l| l.starts_with("@@ "));
+
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+    let mut old_lineno = 0usize;
+    let mut new_lineno = 0usize;
+
+    for line in unified_diff.lines() {
+        if line.starts_with("@@ ") {
+            if let Some(hunk) = current.take() {
+                hunks.push(hunk);
+            }
+            let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) else { continue };
+            old_lineno = old_start;
+            new_lineno = new_start;
+            current = Some(Hunk { old_start, old_lines, new_start, new_lines, header: line.to_string(), lines: Vec::new() });
+            continue;
+        }
+        if line.starts_with("--- ") || line.starts_with("+++ ") {
+            continue;
+        }
+        if line.starts_with('\\') {
+            // `\ No newline at end of file`, emitted for any file whose
+            // last line lacks a trailing newline - not a real diff line.
+            continue;
+        }
+        if current.is_none() {
+            if has_hunk_header {
+                continue;
+            }
+            old_lineno = 0;
+            new_lineno = 1;
+            current = Some(Hunk { old_start: 0, old_lines: 0, new_start: 1, new_lines: 0, header: "@@ -0,0 +1 @@".to_string(), lines: Vec::new() });
+        }
+        let hunk = current.as_mut().unwrap();
+        if line.is_empty() {
+            continue;
+        }
+        let (kind, content, old_no, new_no) = match line.chars().next() {
+            Some('+') => {
+                let no = new_lineno;
+                new_lineno += 1;
+                (DiffLineKind::Added, &line[1..], None, Some(no))
+            }
+            Some('-') => {
+                let no = old_lineno;
+                old_lineno += 1;
+                (DiffLineKind::Removed, &line[1..], Some(no), None)
+            }
+            _ => {
+                let old_no = old_lineno;
+                let new_no = new_lineno;
+                old_lineno += 1;
+                new_lineno += 1;
+                (DiffLineKind::Context, line.strip_prefix(' ').unwrap_or(line), Some(old_no), Some(new_no))
+            }
+        };
+
+        let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, set, ClassStyle::Spaced);
+        let _ = generator.parse_html_for_line_which_includes_newline(&format!("{}\n", content));
+        let html = generator.finalize();
+
+        hunk.lines.push(DiffLine { kind, old_lineno: old_no, new_lineno: new_no, html });
+    }
+    if let Some(mut hunk) = current.take() {
+        if !has_hunk_header {
+            hunk.new_lines = hunk.lines.len();
+            hunk.header = format!("@@ -0,0 +1,{} @@", hunk.new_lines);
+        }
+        hunks.push(hunk);
+    }
+
+    Diff { hunks }
+}
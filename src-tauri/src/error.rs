@@ -0,0 +1,96 @@
+//! Typed classification of Git command failures.
+//!
+//! Most commands in `git` used to fold a failing process into
+//! `format!("... failed: {}", stderr)`, so the frontend had no way to
+//! tell "not a repository" apart from "merge conflict" apart from "auth
+//! failed" short of pattern-matching the message text itself. `GitError`
+//! gives call sites a small, tagged-JSON enum to return instead, built by
+//! classifying the process exit code and stderr the same way `git2`/
+//! `libgit2` wrapper crates bucket their error codes.
+
+use serde::Serialize;
+
+/// A classified Git command failure.
+///
+/// Serializes as a tagged object (`{"kind": "MergeConflict", "files": [...]}`)
+/// so the frontend can match on `kind` instead of string-sniffing stderr.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind")]
+pub enum GitError {
+    /// `path` does not point inside a Git working tree or `.git` directory.
+    NotARepository,
+    /// The operation stopped with unresolved merge conflicts.
+    MergeConflict {
+        /// Paths reported as conflicted, parsed from `CONFLICT` lines.
+        files: Vec<String>,
+    },
+    /// A remote rejected credentials, or no usable SSH key/agent was found.
+    AuthenticationFailed,
+    /// A push was rejected because the remote has commits we don't have.
+    NonFastForward,
+    /// Another Git process (or a stale `index.lock`) is already running.
+    LockHeld,
+    /// Anything else, kept as the raw exit code and stderr.
+    Generic { code: Option<i32>, message: String },
+}
+
+impl std::fmt::Display for GitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitError::NotARepository => write!(f, "Not a Git repository"),
+            GitError::MergeConflict { files } if files.is_empty() => write!(f, "Merge conflict"),
+            GitError::MergeConflict { files } => write!(f, "Merge conflict in {}", files.join(", ")),
+            GitError::AuthenticationFailed => write!(f, "Authentication failed"),
+            GitError::NonFastForward => write!(f, "Updates were rejected (not a fast-forward)"),
+            GitError::LockHeld => write!(f, "Another Git process is already running"),
+            GitError::Generic { message, .. } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<String> for GitError {
+    /// Wraps an already-formatted error message (e.g. from a failed `gix`
+    /// call) as a `Generic` variant, so call sites that mix `GitError`
+    /// with other `Result<_, String>` helpers can still use `?`.
+    fn from(message: String) -> Self {
+        GitError::Generic { code: None, message }
+    }
+}
+
+impl GitError {
+    /// Classifies a failed command's output by exit code and stderr heuristics.
+    ///
+    /// Falls back to `Generic` with the raw exit code and stderr when
+    /// nothing more specific matches.
+    pub fn classify(output: &std::process::Output) -> Self {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        if stderr.contains("not a git repository") {
+            return GitError::NotARepository;
+        }
+        if stderr.contains("Authentication failed")
+            || stderr.contains("Permission denied (publickey)")
+            || stderr.contains("could not read Username")
+            || stderr.contains("could not read Password")
+        {
+            return GitError::AuthenticationFailed;
+        }
+        if stderr.contains("non-fast-forward") || stderr.contains("fetch first") {
+            return GitError::NonFastForward;
+        }
+        if stderr.contains("Unable to create") && stderr.contains(".lock") {
+            return GitError::LockHeld;
+        }
+        if stderr.contains("CONFLICT") || stderr.contains("fix conflicts and then commit") {
+            let files = stderr
+                .lines()
+                .filter_map(|line| line.strip_prefix("CONFLICT"))
+                .filter_map(|line| line.rsplit_once(" in "))
+                .map(|(_, path)| path.trim().to_string())
+                .collect();
+            return GitError::MergeConflict { files };
+        }
+
+        GitError::Generic { code: output.status.code(), message: stderr.trim().to_string() }
+    }
+}
@@ -0,0 +1,154 @@
+//! Forge-aware pull-request creation.
+//!
+//! Parses the `origin` remote's URL (reusing `get_git_remotes`) to figure
+//! out which forge hosts the repository and dispatches to its REST API -
+//! GitHub, or a Gitea/Forgejo instance - instead of making the user leave
+//! the window to open a review.
+
+use serde::Serialize;
+
+/// Which forge API to talk to.
+#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    /// Also covers Forgejo, which mirrors Gitea's API under the same path.
+    Gitea,
+}
+
+/// The forge detected from the repository's `origin` remote.
+#[derive(Serialize)]
+pub struct ForgeInfo {
+    pub kind: ForgeKind,
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+/// Parses `git@host:owner/repo.git` and `https://host/owner/repo(.git)`
+/// remote URLs into `(host, owner, repo)`.
+fn parse_origin_url(url: &str) -> Option<(String, String, String)> {
+    let without_scheme = url
+        .strip_prefix("git@")
+        .map(|rest| rest.replacen(':', "/", 1))
+        .or_else(|| url.strip_prefix("ssh://git@").map(str::to_string))
+        .or_else(|| url.strip_prefix("https://").map(str::to_string))
+        .or_else(|| url.strip_prefix("http://").map(str::to_string))?;
+
+    let without_scheme = without_scheme.trim_end_matches('/').trim_end_matches(".git");
+    let mut parts = without_scheme.splitn(2, '/');
+    let host = parts.next()?;
+    let rest = parts.next()?;
+    let (owner, repo) = rest.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() { return None; }
+    Some((host.to_string(), owner.to_string(), repo.to_string()))
+}
+
+/// Classifies a host as GitHub or a Gitea/Forgejo instance. Anything that
+/// isn't literally `github.com` is assumed to be a self-hosted Gitea -
+/// there's no reliable way to tell Gitea and Forgejo apart from the URL
+/// alone, and they share the same API shape.
+fn classify_host(host: &str) -> ForgeKind {
+    if host.eq_ignore_ascii_case("github.com") { ForgeKind::GitHub } else { ForgeKind::Gitea }
+}
+
+/// Returns the forge host/owner/repo detected from the `origin` remote, so
+/// the UI can label its "open pull request" button.
+///
+/// # Errors
+/// Returns an error if there's no `origin` remote or its URL isn't a
+/// recognized `git@host:owner/repo` or `https://host/owner/repo` form.
+#[tauri::command]
+pub fn get_forge_info(path: String) -> Result<ForgeInfo, String> {
+    let remotes = crate::git::get_git_remotes(path)?;
+    let origin = remotes.iter().find(|r| r.name == "origin").ok_or("No 'origin' remote configured")?;
+    let (host, owner, repo) = parse_origin_url(&origin.url).ok_or_else(|| format!("Could not parse remote URL '{}'", origin.url))?;
+    Ok(ForgeInfo { kind: classify_host(&host), host, owner, repo })
+}
+
+/// Resolves an API token for `remote_url`: a stored credential first (the
+/// same keychain entry HTTPS git operations use), then a forge-specific
+/// environment variable.
+fn resolve_token(remote_url: &str, kind: ForgeKind) -> Option<String> {
+    if let Some(creds) = crate::credentials::lookup_stored(remote_url) {
+        return Some(creds.secret);
+    }
+    let env_var = match kind {
+        ForgeKind::GitHub => "GITHUB_TOKEN",
+        ForgeKind::Gitea => "GITEA_TOKEN",
+    };
+    std::env::var(env_var).ok()
+}
+
+/// Returns the short name of the currently checked-out branch.
+async fn current_branch_name(path: &str) -> Result<String, String> {
+    let repo = gix::open(path).or_else(|_| gix::discover(path)).map_err(|e| e.to_string())?;
+    let head_ref = repo.head().map_err(|e| e.to_string())?;
+    let full_name = head_ref.referent_name().map(|n| n.as_bstr().to_string()).ok_or("HEAD is detached - checkout a branch first")?;
+    Ok(full_name.strip_prefix("refs/heads/").unwrap_or(&full_name).to_string())
+}
+
+/// Opens a pull request from the current branch against `base_branch`.
+///
+/// Resolves an API token first, so a user with no stored credential and no
+/// `GITHUB_TOKEN`/`GITEA_TOKEN` set fails before anything is pushed, then
+/// pushes the current branch (reusing `git_push`'s `--set-upstream`
+/// fallback) so the forge has something to diff against, then POSTs to
+/// GitHub's or Gitea's pull-request endpoint depending on what `origin`
+/// points at.
+///
+/// # Errors
+/// Returns an error if `origin` isn't a recognized forge URL, no API token
+/// is available, the branch can't be pushed, or the forge rejects the
+/// request.
+#[tauri::command]
+pub async fn create_pull_request(path: String, title: String, body: String, base_branch: String, draft: bool) -> Result<String, String> {
+    let info = get_forge_info(path.clone())?;
+    let remotes = crate::git::get_git_remotes(path.clone())?;
+    let origin_url = remotes.iter().find(|r| r.name == "origin").map(|r| r.url.clone()).unwrap_or_default();
+
+    let token = resolve_token(&origin_url, info.kind)
+        .ok_or("No API token found - store one for this remote or set GITHUB_TOKEN/GITEA_TOKEN")?;
+
+    crate::git::git_push(path.clone()).await.map_err(|e| e.to_string())?;
+    let head = current_branch_name(&path).await?;
+
+    let api_url = match info.kind {
+        ForgeKind::GitHub => format!("https://api.github.com/repos/{}/{}/pulls", info.owner, info.repo),
+        ForgeKind::Gitea => format!("https://{}/api/v1/repos/{}/{}/pulls", info.host, info.owner, info.repo),
+    };
+    let payload = serde_json::json!({
+        "title": title,
+        "body": body,
+        "head": head,
+        "base": base_branch,
+        "draft": draft,
+    });
+
+    let client = reqwest::Client::new();
+    let mut request = client.post(&api_url).header("User-Agent", "celerix-git-gui");
+    request = match info.kind {
+        // Gitea/Forgejo personal access tokens authenticate via the
+        // `token` auth scheme - `Bearer` is only accepted for Gitea's
+        // OAuth2 access tokens, not PATs.
+        ForgeKind::GitHub => request.bearer_auth(&token),
+        ForgeKind::Gitea => request.header("Authorization", format!("token {}", token)),
+    };
+    let response = request
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("Forge API returned {}: {}", status, text));
+    }
+
+    let json: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    json.get("html_url")
+        .or_else(|| json.get("url"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| "Forge response did not include a URL".to_string())
+}
@@ -40,6 +40,36 @@ pub struct GitRemote {
     pub url: String,
 }
 
+/// A remote-tracking branch name, split into its remote and branch parts.
+///
+/// Built by matching against the repository's actual configured remotes
+/// rather than splitting on the first `/`, which breaks for remotes or
+/// branch names that themselves contain a slash.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RemoteBranchName {
+    pub remote: String,
+    pub branch: String,
+}
+
+impl RemoteBranchName {
+    /// The `"remote/branch"` form Git expects on the command line.
+    pub fn qualified(&self) -> String {
+        format!("{}/{}", self.remote, self.branch)
+    }
+
+    /// Splits `qualified` (e.g. `"origin/feature/thing"`) into its remote
+    /// and branch parts by matching against `remotes`, preferring the
+    /// longest matching remote name so a remote literally named
+    /// `release/team` isn't shadowed by a shorter remote `release`.
+    pub fn parse(qualified: &str, remotes: &[GitRemote]) -> Option<Self> {
+        remotes
+            .iter()
+            .filter_map(|r| qualified.strip_prefix(&format!("{}/", r.name)).map(|branch| (r.name.clone(), branch.to_string())))
+            .max_by_key(|(remote, _)| remote.len())
+            .map(|(remote, branch)| RemoteBranchName { remote, branch })
+    }
+}
+
 /// Represents a file within a Git commit.
 #[derive(Serialize, Deserialize)]
 pub struct GitCommitFile {
@@ -83,7 +113,7 @@ pub struct GitStatusFile {
     pub is_staged: bool,
 }
 
-/// Information about SSH keys for Git authentication.
+/// Information about an SSH key pair for Git authentication.
 #[derive(Serialize, Deserialize)]
 pub struct SshKeyInfo {
     /// The content of the public key.
@@ -92,25 +122,71 @@ pub struct SshKeyInfo {
     pub has_key: bool,
     /// The file path to the private key.
     pub path: String,
+    /// The key algorithm, parsed from the public key line (e.g. `ssh-ed25519`, `ssh-rsa`).
+    pub algorithm: String,
+    /// The comment on the public key line, if any (often `user@host`).
+    pub comment: String,
 }
 
 /// Executes a git command in the specified directory.
 ///
+/// Every invocation is pointed at this same binary as its `credential.helper`
+/// (see `credentials::run_as_credential_helper_if_requested`), so a token
+/// saved via `store_remote_credentials` is filled in transparently for any
+/// HTTPS transfer - git only actually spawns the helper when a transfer asks
+/// for credentials, so this is a no-op for commands that don't need one.
+///
+/// This is the single choke point nearly every git-reading/mutating command
+/// goes through, so it's also where timing and invocation logging live
+/// (see `logging`): the exact `git <args>` invoked, how long it took, and -
+/// on failure - the stderr, logged once here instead of being hand-wrapped
+/// at each call site.
+///
+/// Both a failure to spawn `git` at all and a non-zero exit reported back
+/// by it are forwarded to `telemetry::report_command_error`, so real git
+/// failures (auth, conflicts, rejected pushes, ...) show up in Sentry the
+/// same as spawn failures, not just the latter.
+///
 /// # Arguments
 /// * `path` - The working directory for the git command.
 /// * `args` - The arguments to pass to the git command.
 ///
 /// # Errors
 /// Returns an error message if the command fails to execute.
-async fn run_git_command(path: &str, args: &[&str]) -> Result<std::process::Output, String> {
-    Command::new("git")
+pub(crate) async fn run_git_command(path: &str, args: &[&str]) -> Result<std::process::Output, String> {
+    let credential_helper = std::env::current_exe()
+        .map(|exe| format!("{} {}", exe.to_string_lossy(), crate::credentials::CREDENTIAL_HELPER_FLAG))
+        .map_err(|e| e.to_string())?;
+
+    let command = *args.get(0).unwrap_or(&"git");
+    let start = std::time::Instant::now();
+    log::debug!("{} invoking: git {}", command, args.join(" "));
+
+    let output = Command::new("git")
         .arg("-C").arg(path)
+        .arg("-c").arg(format!("credential.helper={}", credential_helper))
+        .arg("-c").arg("credential.useHttpPath=true")
         .args(args)
         .env("GIT_TERMINAL_PROMPT", "0")
         .env("GIT_SSH_COMMAND", "ssh -o BatchMode=yes")
         .output()
         .await
-        .map_err(|e| format!("Failed to execute git {}: {}", args.get(0).unwrap_or(&"command"), e))
+        .map_err(|e| format!("Failed to execute git {}: {}", command, e));
+
+    let elapsed = start.elapsed();
+    match &output {
+        Ok(output) if output.status.success() => log::debug!("{} finished in {:?}", command, elapsed),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            log::warn!("{} failed after {:?}: {}", command, elapsed, stderr);
+            crate::telemetry::report_command_error(command, Some(path), &stderr);
+        }
+        Err(err) => {
+            log::warn!("{} failed to spawn after {:?}: {}", command, elapsed, err);
+            crate::telemetry::report_command_error(command, Some(path), err);
+        }
+    }
+    output
 }
 
 /// Clears the local avatar cache.
@@ -265,7 +341,13 @@ pub async fn get_git_status(path: String) -> Result<Vec<GitStatusFile>, String>
 /// # Errors
 /// Returns an error if the commit fails.
 #[tauri::command]
-pub async fn git_commit(path: String, subject: String, body: String, amend: bool) -> Result<(), String> {
+pub async fn git_commit(
+    cache: tauri::State<'_, crate::cache::GitQueryCache>,
+    path: String,
+    subject: String,
+    body: String,
+    amend: bool,
+) -> Result<(), crate::error::GitError> {
     let mut args = vec!["commit"];
     if amend { args.push("--amend"); }
     let message = if body.is_empty() { subject } else { format!("{}\n\n{}", subject, body) };
@@ -273,8 +355,9 @@ pub async fn git_commit(path: String, subject: String, body: String, amend: bool
     args.push(&message);
     let output = run_git_command(&path, &args).await?;
     if !output.status.success() {
-        return Err(format!("Git commit failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
+    cache.invalidate_repo(&path).await;
     Ok(())
 }
 
@@ -283,9 +366,9 @@ pub async fn git_commit(path: String, subject: String, body: String, amend: bool
 /// # Errors
 /// Returns an error if the git add command fails.
 #[tauri::command]
-pub async fn git_stage_file(path: String, file_path: String) -> Result<(), String> {
+pub async fn git_stage_file(path: String, file_path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["add", &file_path]).await?;
-    if !output.status.success() { return Err(format!("Git add failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
@@ -294,9 +377,9 @@ pub async fn git_stage_file(path: String, file_path: String) -> Result<(), Strin
 /// # Errors
 /// Returns an error if the git add command fails.
 #[tauri::command]
-pub async fn git_stage_all(path: String) -> Result<(), String> {
+pub async fn git_stage_all(path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["add", "-A"]).await?;
-    if !output.status.success() { return Err(format!("Git add -A failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
@@ -305,9 +388,64 @@ pub async fn git_stage_all(path: String) -> Result<(), String> {
 /// # Errors
 /// Returns an error if the git reset command fails.
 #[tauri::command]
-pub async fn git_unstage_file(path: String, file_path: String) -> Result<(), String> {
+pub async fn git_unstage_file(path: String, file_path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["reset", "HEAD", "--", &file_path]).await?;
-    if !output.status.success() { return Err(format!("Git reset failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
+    Ok(())
+}
+
+/// Stages or unstages a single hunk (or a synthesized subset of its lines)
+/// by applying `patch` to the index with `git apply --cached`.
+///
+/// `patch` must be a single-hunk unified diff for `file_path` - the
+/// frontend builds it from the structured diff (see `diff::Diff`), either
+/// passing a whole hunk through unmodified or synthesizing one containing
+/// only the selected `+`/`-` lines with recomputed `@@` counts for
+/// line-level staging. Pass `reverse: true` to unstage instead.
+///
+/// # Errors
+/// Returns an error if the patch fails to apply - almost always context
+/// drift between the patch and the current index.
+#[tauri::command]
+pub async fn git_stage_hunk(path: String, file_path: String, patch: String, reverse: bool) -> Result<(), crate::error::GitError> {
+    let mut args = vec!["apply", "--cached", "--unidiff-zero"];
+    if reverse { args.push("--reverse"); }
+    args.push("-");
+
+    let mut child = tokio::process::Command::new("git")
+        .arg("-C").arg(&path)
+        .args(&args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn git apply: {}", e))?;
+
+    {
+        use tokio::io::AsyncWriteExt;
+        let stdin = child.stdin.as_mut().ok_or("Failed to open git apply stdin")?;
+        stdin.write_all(patch.as_bytes()).await.map_err(|e| e.to_string())?;
+    }
+
+    let output = child.wait_with_output().await.map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        // Keep the generic classification (lock held, not a repo, ...) as-is,
+        // but fold in the context-drift hint for the common case where
+        // `classify` has nothing more specific to say.
+        return Err(match crate::error::GitError::classify(&output) {
+            crate::error::GitError::Generic { code, message } => crate::error::GitError::Generic {
+                code,
+                message: format!(
+                    "Failed to apply {} for '{}' (likely context drift between the patch and the current index): {}",
+                    if reverse { "unstage" } else { "stage" },
+                    file_path,
+                    message
+                ),
+            },
+            other => other,
+        });
+    }
     Ok(())
 }
 
@@ -316,9 +454,9 @@ pub async fn git_unstage_file(path: String, file_path: String) -> Result<(), Str
 /// # Errors
 /// Returns an error if the git reset command fails.
 #[tauri::command]
-pub async fn git_unstage_all(path: String) -> Result<(), String> {
+pub async fn git_unstage_all(path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["reset", "HEAD"]).await?;
-    if !output.status.success() { return Err(format!("Git reset HEAD failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
@@ -331,25 +469,25 @@ pub async fn git_unstage_all(path: String) -> Result<(), String> {
 /// # Errors
 /// Returns an error if checkout or clean commands fail.
 #[tauri::command]
-pub async fn git_discard_changes(path: String, files: Vec<String>) -> Result<(), String> {
+pub async fn git_discard_changes(path: String, files: Vec<String>) -> Result<(), crate::error::GitError> {
     if files.is_empty() { return Ok(()); }
-    
+
     let mut args = vec!["checkout".to_string(), "--".to_string()];
     for file in &files {
         args.push(file.clone());
     }
-    
+
     let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     let output = run_git_command(&path, &arg_refs).await?;
     if !output.status.success() {
-        return Err(format!("Git discard changes failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
-    
+
     let status = get_git_status(path.clone()).await?;
     let untracked: Vec<String> = files.into_iter()
         .filter(|f| status.iter().any(|s| &s.path == f && s.status.trim() == "??"))
         .collect();
-    
+
     if !untracked.is_empty() {
         let mut clean_args = vec!["clean".to_string(), "-f".to_string(), "--".to_string()];
         for file in &untracked {
@@ -358,7 +496,7 @@ pub async fn git_discard_changes(path: String, files: Vec<String>) -> Result<(),
         let clean_arg_refs: Vec<&str> = clean_args.iter().map(|s| s.as_str()).collect();
         let output = run_git_command(&path, &clean_arg_refs).await?;
         if !output.status.success() {
-            return Err(format!("Git clean failed: {}", String::from_utf8_lossy(&output.stderr)));
+            return Err(crate::error::GitError::classify(&output));
         }
     }
 
@@ -377,14 +515,14 @@ pub async fn git_discard_changes(path: String, files: Vec<String>) -> Result<(),
 ///
 /// Returns an error if stashing fails.
 #[tauri::command]
-pub async fn git_stash_save(path: String, files: Vec<String>, message: Option<String>) -> Result<(), String> {
+pub async fn git_stash_save(cache: tauri::State<'_, crate::cache::GitQueryCache>, path: String, files: Vec<String>, message: Option<String>) -> Result<(), crate::error::GitError> {
     if files.is_empty() { return Ok(()); }
 
     // Stashing specific files is a bit involved in git.
     // One common way:
     // 1. Stage the files we want to stash
     // 2. git stash push --staged -m "message"
-    
+
     // First, stage the files
     for file in &files {
         git_stage_file(path.clone(), file.clone()).await?;
@@ -401,9 +539,10 @@ pub async fn git_stash_save(path: String, files: Vec<String>, message: Option<St
     let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     let output = run_git_command(&path, &arg_refs).await?;
     if !output.status.success() {
-        return Err(format!("Git stash push --staged failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
 
+    cache.invalidate_repo(&path).await;
     Ok(())
 }
 
@@ -418,12 +557,13 @@ pub async fn git_stash_save(path: String, files: Vec<String>, message: Option<St
 ///
 /// Returns an error if the stash entry cannot be dropped.
 #[tauri::command]
-pub async fn git_stash_drop(path: String, index: usize) -> Result<(), String> {
+pub async fn git_stash_drop(cache: tauri::State<'_, crate::cache::GitQueryCache>, path: String, index: usize) -> Result<(), crate::error::GitError> {
     let stash_ref = format!("stash@{{{}}}", index);
     let output = run_git_command(&path, &["stash", "drop", &stash_ref]).await?;
     if !output.status.success() {
-        return Err(format!("Git stash drop failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
+    cache.invalidate_repo(&path).await;
     Ok(())
 }
 
@@ -438,12 +578,13 @@ pub async fn git_stash_drop(path: String, index: usize) -> Result<(), String> {
 ///
 /// Returns an error if the stash entry cannot be popped.
 #[tauri::command]
-pub async fn git_stash_pop(path: String, index: usize) -> Result<(), String> {
+pub async fn git_stash_pop(cache: tauri::State<'_, crate::cache::GitQueryCache>, path: String, index: usize) -> Result<(), crate::error::GitError> {
     let stash_ref = format!("stash@{{{}}}", index);
     let output = run_git_command(&path, &["stash", "pop", &stash_ref]).await?;
     if !output.status.success() {
-        return Err(format!("Git stash pop failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
+    cache.invalidate_repo(&path).await;
     Ok(())
 }
 
@@ -493,6 +634,34 @@ pub async fn get_git_diff(path: String, file_path: String) -> Result<String, Str
     Ok(diff)
 }
 
+/// Returns `get_git_diff`'s output parsed into a typed, syntax-highlighted
+/// `Diff`, so the frontend can render code coloring via CSS classes instead
+/// of reimplementing a highlighter in JS.
+///
+/// # Errors
+/// Returns an error under the same conditions as `get_git_diff`.
+#[tauri::command]
+pub async fn get_git_diff_structured(path: String, file_path: String) -> Result<crate::diff::Diff, String> {
+    let raw = get_git_diff(path, file_path.clone()).await?;
+    Ok(crate::diff::parse_and_highlight(&raw, &file_path))
+}
+
+/// Returns `get_commit_file_diff`'s output parsed into a typed,
+/// syntax-highlighted `Diff`.
+///
+/// # Errors
+/// Returns an error under the same conditions as `get_commit_file_diff`.
+#[tauri::command]
+pub async fn get_commit_file_diff_structured(
+    cache: tauri::State<'_, crate::cache::GitQueryCache>,
+    path: String,
+    hash: String,
+    file_path: String,
+) -> Result<crate::diff::Diff, String> {
+    let raw = get_commit_file_diff(cache, path, hash, file_path.clone()).await?;
+    Ok(crate::diff::parse_and_highlight(&raw, &file_path))
+}
+
 /// Returns a list of local Git branches.
 ///
 /// # Arguments
@@ -530,7 +699,18 @@ pub fn get_git_branches(path: String) -> Result<Vec<GitBranch>, String> {
 ///
 /// Returns an error if the Git log command fails.
 #[tauri::command]
-pub async fn get_git_commits(path: String) -> Result<Vec<GitCommit>, String> {
+pub async fn get_git_commits(cache: tauri::State<'_, crate::cache::GitQueryCache>, path: String) -> Result<Vec<GitCommit>, String> {
+    if let Some(cached) = cache.get(&path, "get_git_commits", "").await {
+        return serde_json::from_str(&cached).map_err(|e| e.to_string());
+    }
+    let commits = get_git_commits_uncached(path.clone()).await?;
+    if let Ok(json) = serde_json::to_string(&commits) {
+        cache.put(&path, "get_git_commits", "", json).await;
+    }
+    Ok(commits)
+}
+
+async fn get_git_commits_uncached(path: String) -> Result<Vec<GitCommit>, String> {
     // Use a custom format with a unique delimiter to handle multi-line bodies and special characters
     // %H: full commit hash
     // %an: author name
@@ -606,7 +786,18 @@ pub async fn get_git_commits(path: String) -> Result<Vec<GitCommit>, String> {
 ///
 /// Returns an error if the Git show command fails.
 #[tauri::command]
-pub async fn get_commit_files(path: String, hash: String) -> Result<Vec<GitCommitFile>, String> {
+pub async fn get_commit_files(cache: tauri::State<'_, crate::cache::GitQueryCache>, path: String, hash: String) -> Result<Vec<GitCommitFile>, String> {
+    if let Some(cached) = cache.get(&path, "get_commit_files", &hash).await {
+        return serde_json::from_str(&cached).map_err(|e| e.to_string());
+    }
+    let files = get_commit_files_uncached(path.clone(), hash.clone()).await?;
+    if let Ok(json) = serde_json::to_string(&files) {
+        cache.put(&path, "get_commit_files", &hash, json).await;
+    }
+    Ok(files)
+}
+
+async fn get_commit_files_uncached(path: String, hash: String) -> Result<Vec<GitCommitFile>, String> {
     let output = run_git_command(&path, &["show", "--name-status", "--format=", &hash]).await?;
     if !output.status.success() {
         return Err(format!("Git show failed: {}", String::from_utf8_lossy(&output.stderr)));
@@ -638,7 +829,17 @@ pub async fn get_commit_files(path: String, hash: String) -> Result<Vec<GitCommi
 ///
 /// Returns an error if the Git show diff command fails.
 #[tauri::command]
-pub async fn get_commit_file_diff(path: String, hash: String, file_path: String) -> Result<String, String> {
+pub async fn get_commit_file_diff(cache: tauri::State<'_, crate::cache::GitQueryCache>, path: String, hash: String, file_path: String) -> Result<String, String> {
+    let cache_args = format!("{}\u{1}{}", hash, file_path);
+    if let Some(cached) = cache.get(&path, "get_commit_file_diff", &cache_args).await {
+        return Ok(cached);
+    }
+    let diff = get_commit_file_diff_uncached(path.clone(), hash, file_path).await?;
+    cache.put(&path, "get_commit_file_diff", &cache_args, diff.clone()).await;
+    Ok(diff)
+}
+
+async fn get_commit_file_diff_uncached(path: String, hash: String, file_path: String) -> Result<String, String> {
     // Actually, we want the diff. 'git show hash -- file_path' shows the diff.
     let output = run_git_command(&path, &["show", "--format=", &hash, "--", &file_path]).await?;
     if !output.status.success() {
@@ -683,9 +884,9 @@ pub fn get_git_remotes(path: String) -> Result<Vec<GitRemote>, String> {
 ///
 /// Returns an error if the Git checkout command fails.
 #[tauri::command]
-pub async fn switch_branch(path: String, branch_name: String) -> Result<(), String> {
+pub async fn switch_branch(path: String, branch_name: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["checkout", &branch_name]).await?;
-    if !output.status.success() { return Err(format!("Git checkout failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
@@ -699,7 +900,8 @@ pub async fn switch_branch(path: String, branch_name: String) -> Result<(), Stri
 ///
 /// Returns an error if the repository cannot be opened.
 #[tauri::command]
-pub fn get_git_remote_branches(path: String) -> Result<Vec<String>, String> {
+pub fn get_git_remote_branches(path: String) -> Result<Vec<RemoteBranchName>, String> {
+    let remotes = get_git_remotes(path.clone())?;
     let repo = gix::open(&path).or_else(|_| gix::discover(&path)).map_err(|e| e.to_string())?;
     let references = repo.references().map_err(|e| e.to_string())?;
     let mut branches = Vec::new();
@@ -708,7 +910,10 @@ pub fn get_git_remote_branches(path: String) -> Result<Vec<String>, String> {
         let reference = res.map_err(|e| e.to_string())?;
         let full_name = reference.name().as_bstr().to_string();
         let name = if full_name.starts_with("refs/remotes/") { full_name["refs/remotes/".len()..].to_string() } else { full_name };
-        if !name.ends_with("/HEAD") { branches.push(name); }
+        if name.ends_with("/HEAD") { continue; }
+        if let Some(remote_branch) = RemoteBranchName::parse(&name, &remotes) {
+            branches.push(remote_branch);
+        }
     }
     Ok(branches)
 }
@@ -781,62 +986,191 @@ pub async fn get_git_stashes(path: String) -> Result<Vec<GitStash>, String> {
 ///
 /// Returns an error if the checkout command fails or if the local branch already exists.
 #[tauri::command]
-pub async fn git_checkout_remote_branch(path: String, remote_branch: String, new_branch_name: Option<String>) -> Result<(), String> {
+pub async fn git_checkout_remote_branch(path: String, remote_branch: RemoteBranchName, new_branch_name: Option<String>) -> Result<(), crate::error::GitError> {
+    let qualified = remote_branch.qualified();
     let exists = {
         let repo = gix::open(&path).or_else(|_| gix::discover(&path)).map_err(|e| e.to_string())?;
-        let default_local_name = if let Some(pos) = remote_branch.find('/') { &remote_branch[pos + 1..] } else { &remote_branch };
-        let target_local_name = new_branch_name.as_deref().unwrap_or(default_local_name);
+        let target_local_name = new_branch_name.as_deref().unwrap_or(&remote_branch.branch);
         let references = repo.references().map_err(|e| e.to_string())?;
         let local_branches = references.local_branches().map_err(|e| e.to_string())?;
         let mut exists = false;
         for res in local_branches { if let Ok(reference) = res { if reference.name().as_bstr().to_string() == target_local_name { exists = true; break; } } }
         exists
     };
-    let default_local_name = if let Some(pos) = remote_branch.find('/') { &remote_branch[pos + 1..] } else { &remote_branch };
-    let target_local_name = new_branch_name.as_deref().unwrap_or(default_local_name);
+    let target_local_name = new_branch_name.as_deref().unwrap_or(&remote_branch.branch);
     let mut args = vec!["checkout"];
     if exists {
-        if new_branch_name.is_some() { return Err(format!("Branch '{}' exists.", target_local_name)); }
+        if new_branch_name.is_some() { return Err(format!("Branch '{}' exists.", target_local_name).into()); }
         args.push(target_local_name);
-    } else { args.extend_from_slice(&["-b", target_local_name, "--track", &remote_branch]); }
+    } else { args.extend_from_slice(&["-b", target_local_name, "--track", &qualified]); }
     let output = run_git_command(&path, &args).await?;
-    if !output.status.success() { return Err(format!("Git checkout failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
-/// Returns SSH key information.
+/// Sets `local_branch`'s upstream tracking branch.
+///
+/// Lets the UI resolve the "no upstream" case `git_push` errors out on
+/// when more than one remote is configured and it can't guess which one
+/// to set up tracking against.
+///
+/// # Arguments
+///
+/// * `path` - Path to the Git repository.
+/// * `local_branch` - The local branch to set an upstream for.
+/// * `remote_branch` - The remote-tracking branch to track.
 ///
 /// # Errors
 ///
-/// Returns an error if the home directory cannot be determined.
+/// Returns an error if the local branch or remote-tracking branch doesn't exist.
 #[tauri::command]
-pub async fn get_ssh_key_info() -> Result<SshKeyInfo, String> {
+pub async fn set_branch_upstream(path: String, local_branch: String, remote_branch: RemoteBranchName) -> Result<(), crate::error::GitError> {
+    let output = run_git_command(&path, &["branch", &format!("--set-upstream-to={}", remote_branch.qualified()), &local_branch]).await?;
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
+    Ok(())
+}
+
+/// Parses an OpenSSH public key line (`<algorithm> <base64> [comment]`)
+/// into its algorithm and comment parts.
+fn parse_pub_key_line(line: &str) -> (String, String) {
+    let mut parts = line.split_whitespace();
+    let algorithm = parts.next().unwrap_or("").to_string();
+    let comment = parts.skip(1).collect::<Vec<_>>().join(" ");
+    (algorithm, comment)
+}
+
+/// Returns the home-relative `.ssh` directory.
+fn ssh_dir() -> Result<PathBuf, String> {
     let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).map_err(|_| "No home dir")?;
-    let ssh_path = PathBuf::from(home).join(".ssh");
-    let (key_path, pub_key_path) = (ssh_path.join("id_ed25519"), ssh_path.join("id_ed25519.pub"));
-    if key_path.exists() && pub_key_path.exists() {
-        let public_key = fs::read_to_string(pub_key_path).await.map_err(|e| e.to_string())?;
-        Ok(SshKeyInfo { public_key, has_key: true, path: key_path.to_string_lossy().to_string() })
-    } else {
-        Ok(SshKeyInfo { public_key: "".to_string(), has_key: false, path: key_path.to_string_lossy().to_string() })
+    Ok(PathBuf::from(home).join(".ssh"))
+}
+
+/// Builds an `SshKeyInfo` for the key pair at `key_path`/`key_path.pub`.
+async fn read_key_pair(key_path: &std::path::Path) -> Result<SshKeyInfo, String> {
+    let mut pub_key_path = key_path.as_os_str().to_owned();
+    pub_key_path.push(".pub");
+    let public_key = fs::read_to_string(&pub_key_path).await.map_err(|e| e.to_string())?;
+    let (algorithm, comment) = parse_pub_key_line(public_key.trim());
+    Ok(SshKeyInfo { public_key, has_key: true, path: key_path.to_string_lossy().to_string(), algorithm, comment })
+}
+
+/// Returns every SSH key pair found in `~/.ssh`: the well-known
+/// `id_ed25519`/`id_rsa`/`id_ecdsa` names, plus any other `*.pub` file with
+/// a matching private key.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+#[tauri::command]
+pub async fn get_ssh_key_info() -> Result<Vec<SshKeyInfo>, String> {
+    let ssh_path = ssh_dir()?;
+    if !ssh_path.exists() { return Ok(Vec::new()); }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut keys = Vec::new();
+
+    for well_known in ["id_ed25519", "id_rsa", "id_ecdsa"] {
+        let key_path = ssh_path.join(well_known);
+        if key_path.exists() && key_path.with_extension("pub").exists() {
+            keys.push(read_key_pair(&key_path).await?);
+            seen.insert(key_path);
+        }
     }
+
+    let mut entries = fs::read_dir(&ssh_path).await.map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next_entry().await.map_err(|e| e.to_string())? {
+        let pub_path = entry.path();
+        if pub_path.extension().and_then(|e| e.to_str()) != Some("pub") { continue; }
+        let key_path = pub_path.with_extension("");
+        if seen.contains(&key_path) || !key_path.exists() { continue; }
+        keys.push(read_key_pair(&key_path).await?);
+        seen.insert(key_path);
+    }
+
+    Ok(keys)
 }
 
-/// Generates a new SSH key pair.
+/// Generates a new `id_ed25519` SSH key pair with no passphrase.
 ///
 /// # Errors
 ///
 /// Returns an error if key generation fails or if a key already exists.
 #[tauri::command]
 pub async fn generate_ssh_key() -> Result<SshKeyInfo, String> {
-    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE")).map_err(|_| "No home dir")?;
-    let ssh_path = PathBuf::from(home).join(".ssh");
+    generate_ssh_key_ex("ed25519".to_string(), None, None, "celerix-app".to_string(), "id_ed25519".to_string()).await
+}
+
+/// Generates a new SSH key pair with a choice of algorithm, key size, and
+/// an optional passphrase.
+///
+/// # Arguments
+/// * `algorithm` - `"ed25519"`, `"rsa"`, or `"ecdsa"`.
+/// * `bits` - Key size in bits; ignored for `ed25519`. Defaults to 4096 for
+///   `rsa` and 256 for `ecdsa` when not given.
+/// * `passphrase` - Protects the private key if non-empty.
+/// * `comment` - The comment embedded in the public key.
+/// * `filename` - File name under `~/.ssh` for the private key (e.g. `id_rsa_work`).
+///
+/// # Errors
+///
+/// Returns an error if the algorithm is unrecognized, a key already exists
+/// at that path, or `ssh-keygen` fails.
+#[tauri::command]
+pub async fn generate_ssh_key_ex(
+    algorithm: String,
+    bits: Option<u32>,
+    passphrase: Option<String>,
+    comment: String,
+    filename: String,
+) -> Result<SshKeyInfo, String> {
+    let key_type = match algorithm.as_str() {
+        "ed25519" => "ed25519",
+        "rsa" => "rsa",
+        "ecdsa" => "ecdsa",
+        other => return Err(format!("Unsupported SSH key algorithm '{}'", other)),
+    };
+    let bits = bits.unwrap_or(match key_type { "rsa" => 4096, "ecdsa" => 256, _ => 0 });
+
+    let ssh_path = ssh_dir()?;
     if !ssh_path.exists() { fs::create_dir_all(&ssh_path).await.map_err(|e| e.to_string())?; }
-    let key_path = ssh_path.join("id_ed25519");
-    if key_path.exists() { return Err("SSH key exists".to_string()); }
-    let output = Command::new("ssh-keygen").args(&["-t", "ed25519", "-f", &key_path.to_string_lossy(), "-N", "", "-C", "celerix-app"]).output().await.map_err(|e| e.to_string())?;
+    let key_path = ssh_path.join(&filename);
+    if key_path.exists() { return Err(format!("SSH key '{}' already exists", filename)); }
+
+    let mut args = vec!["-t".to_string(), key_type.to_string(), "-f".to_string(), key_path.to_string_lossy().to_string()];
+    if key_type != "ed25519" && bits > 0 {
+        args.push("-b".to_string());
+        args.push(bits.to_string());
+    }
+    args.push("-C".to_string());
+    args.push(comment);
+
+    let mut command = Command::new("ssh-keygen");
+    command.args(&args);
+
+    // An empty passphrase is never sensitive, so it can stay a plain `-N`
+    // argument. A real passphrase must not go on argv - it'd sit in
+    // `ps`/`/proc/<pid>/cmdline` for the process's lifetime - so instead
+    // omit `-N` (which makes ssh-keygen prompt twice, for the new
+    // passphrase and its confirmation) and answer both prompts through the
+    // same askpass-socket mechanism `auth::add_key_to_agent` uses.
+    let _stop_listener = match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => {
+            let (port, stop) = crate::auth::spawn_fixed_answer_listener(passphrase).await.map_err(|e| e.to_string())?;
+            let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+            command.env(crate::auth::ASKPASS_PORT_ENV, port.to_string());
+            command.env("SSH_ASKPASS", exe.to_string_lossy().to_string());
+            command.env("SSH_ASKPASS_REQUIRE", "force");
+            Some(stop)
+        }
+        None => {
+            command.arg("-N").arg("");
+            None
+        }
+    };
+
+    let output = command.output().await.map_err(|e| e.to_string())?;
     if !output.status.success() { return Err(format!("ssh-keygen failed: {}", String::from_utf8_lossy(&output.stderr))); }
-    get_ssh_key_info().await
+    read_key_pair(&key_path).await
 }
 
 /// Fetches from all remotes.
@@ -849,9 +1183,9 @@ pub async fn generate_ssh_key() -> Result<SshKeyInfo, String> {
 ///
 /// Returns an error if the Git fetch command fails.
 #[tauri::command]
-pub async fn git_fetch(path: String) -> Result<(), String> {
+pub async fn git_fetch(path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["fetch", "--all"]).await?;
-    if !output.status.success() { return Err(format!("Git fetch failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
@@ -865,9 +1199,9 @@ pub async fn git_fetch(path: String) -> Result<(), String> {
 ///
 /// Returns an error if the Git pull command fails.
 #[tauri::command]
-pub async fn git_pull(path: String) -> Result<(), String> {
+pub async fn git_pull(path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["pull"]).await?;
-    if !output.status.success() { return Err(format!("Git pull failed: {}", String::from_utf8_lossy(&output.stderr))); }
+    if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     Ok(())
 }
 
@@ -881,7 +1215,7 @@ pub async fn git_pull(path: String) -> Result<(), String> {
 ///
 /// Returns an error if the Git push command fails.
 #[tauri::command]
-pub async fn git_push(path: String) -> Result<(), String> {
+pub async fn git_push(path: String) -> Result<(), crate::error::GitError> {
     let output = run_git_command(&path, &["push"]).await?;
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -898,10 +1232,10 @@ pub async fn git_push(path: String) -> Result<(), String> {
                         full_name
                     }
                 } else {
-                    return Err("Could not determine current branch name".to_string());
+                    return Err("Could not determine current branch name".to_string().into());
                 }
             };
-            
+
             // Get remotes
             let remotes = get_git_remotes(path.clone())?;
             if remotes.len() == 1 {
@@ -910,15 +1244,15 @@ pub async fn git_push(path: String) -> Result<(), String> {
                 if output.status.success() {
                     return Ok(());
                 } else {
-                    return Err(format!("Git push --set-upstream failed: {}", String::from_utf8_lossy(&output.stderr)));
+                    return Err(crate::error::GitError::classify(&output));
                 }
             } else if remotes.is_empty() {
-                return Err("No remotes configured to push to.".to_string());
+                return Err("No remotes configured to push to.".to_string().into());
             } else {
-                return Err(format!("Branch '{}' has no upstream. Please set it manually or choose a remote.", branch_name));
+                return Err(format!("Branch '{}' has no upstream. Please set it manually or choose a remote.", branch_name).into());
             }
         }
-        return Err(format!("Git push failed: {}", stderr));
+        return Err(crate::error::GitError::classify(&output));
     }
     Ok(())
 }
@@ -937,7 +1271,7 @@ pub async fn git_push(path: String) -> Result<(), String> {
 ///
 /// Returns an error if the tag creation or push fails.
 #[tauri::command]
-pub async fn git_create_tag(path: String, tag_name: String, commit_hash: String, message: Option<String>, push_all: bool) -> Result<(), String> {
+pub async fn git_create_tag(path: String, tag_name: String, commit_hash: String, message: Option<String>, push_all: bool) -> Result<(), crate::error::GitError> {
     let mut args = vec!["tag".to_string()];
     if let Some(msg) = message {
         if !msg.trim().is_empty() {
@@ -956,13 +1290,13 @@ pub async fn git_create_tag(path: String, tag_name: String, commit_hash: String,
     let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
     let output = run_git_command(&path, &arg_refs).await?;
     if !output.status.success() {
-        return Err(format!("Git tag failed: {}", String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
 
     if push_all {
         let output = run_git_command(&path, &["push", "--tags"]).await?;
         if !output.status.success() {
-            return Err(format!("Git push --tags failed: {}", String::from_utf8_lossy(&output.stderr)));
+            return Err(crate::error::GitError::classify(&output));
         }
     } else {
         // Push only the new tag to the current remote
@@ -973,7 +1307,7 @@ pub async fn git_create_tag(path: String, tag_name: String, commit_hash: String,
             let remote_name = remotes.iter().find(|r| r.name == "origin").map(|r| r.name.as_str()).unwrap_or(remotes[0].name.as_str());
             let output = run_git_command(&path, &["push", remote_name, &tag_name]).await?;
             if !output.status.success() {
-                return Err(format!("Git push tag failed: {}", String::from_utf8_lossy(&output.stderr)));
+                return Err(crate::error::GitError::classify(&output));
             }
         }
     }
@@ -994,13 +1328,13 @@ pub async fn git_create_tag(path: String, tag_name: String, commit_hash: String,
 ///
 /// Returns an error if branch creation or checkout fails.
 #[tauri::command]
-pub async fn git_create_branch(path: String, branch_name: String, start_point: String, checkout: bool) -> Result<(), String> {
+pub async fn git_create_branch(path: String, branch_name: String, start_point: String, checkout: bool) -> Result<(), crate::error::GitError> {
     if checkout {
         let output = run_git_command(&path, &["checkout", "-b", &branch_name, &start_point]).await?;
-        if !output.status.success() { return Err(format!("Git checkout -b failed: {}", String::from_utf8_lossy(&output.stderr))); }
+        if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     } else {
         let output = run_git_command(&path, &["branch", &branch_name, &start_point]).await?;
-        if !output.status.success() { return Err(format!("Git branch failed: {}", String::from_utf8_lossy(&output.stderr))); }
+        if !output.status.success() { return Err(crate::error::GitError::classify(&output)); }
     }
     Ok(())
 }
@@ -1017,7 +1351,7 @@ pub async fn git_create_branch(path: String, branch_name: String, start_point: S
 ///
 /// Returns an error if branch deletion fails or if trying to delete the current branch.
 #[tauri::command]
-pub async fn git_delete_branch(path: String, branch_name: String, delete_remote: bool) -> Result<(), String> {
+pub async fn git_delete_branch(path: String, branch_name: String, delete_remote: bool) -> Result<(), crate::error::GitError> {
     // Check if it's the current branch
     {
         let repo = gix::open(&path).or_else(|_| gix::discover(&path)).map_err(|e| e.to_string())?;
@@ -1026,7 +1360,7 @@ pub async fn git_delete_branch(path: String, branch_name: String, delete_remote:
             let full_name = head_name.as_bstr().to_string();
             let current_name = if full_name.starts_with("refs/heads/") { &full_name["refs/heads/".len()..] } else { &full_name };
             if current_name == branch_name {
-                return Err("Cannot delete the currently active branch.".to_string());
+                return Err("Cannot delete the currently active branch.".to_string().into());
             }
         }
     }
@@ -1034,7 +1368,7 @@ pub async fn git_delete_branch(path: String, branch_name: String, delete_remote:
     // Delete local branch
     let output = run_git_command(&path, &["branch", "-D", &branch_name]).await?;
     if !output.status.success() {
-        return Err(format!("Failed to delete local branch '{}': {}", branch_name, String::from_utf8_lossy(&output.stderr)));
+        return Err(crate::error::GitError::classify(&output));
     }
 
     if delete_remote {
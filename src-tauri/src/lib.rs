@@ -1,26 +1,61 @@
+mod auth;
+mod background;
+mod branch_cleanup;
+mod cache;
+mod credentials;
+mod diff;
+mod error;
+mod forge;
 mod git;
+mod logging;
+mod patch_email;
+mod repo_registry;
+mod telemetry;
+mod vbranch;
+mod watcher;
+mod worktree;
 
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{TrayIconBuilder, TrayIconEvent},
-    Manager,
+    Manager, WindowEvent,
 };
 
 pub fn run() {
+    if auth::is_askpass_invocation() {
+        auth::run_as_askpass_if_requested();
+    }
+    if credentials::is_credential_helper_invocation() {
+        credentials::run_as_credential_helper_if_requested();
+    }
+
+    telemetry::init();
+
     tauri::Builder::default()
+        .manage(watcher::WatcherState::default())
+        .manage(auth::PendingPrompts::default())
+        .manage(cache::GitQueryCache::default())
+        .manage(vbranch::IndexLocks::default())
         .setup(|app| {
             let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let show_i = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
-            let hide_i = MenuItem::with_id(app, "hide", "Hide", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_i, &hide_i, &quit_i])?;
+            let show_hide_i = MenuItem::with_id(app, "show_hide", "Hide", true, None::<&str>)?;
+            let menu = Menu::with_items(app, &[&show_hide_i, &quit_i])?;
             let _tray = TrayIconBuilder::new()
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&menu)
                 .show_menu_on_left_click(false)
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "quit" => { app.exit(0); }
-                    "show" => { if let Some(window) = app.get_webview_window("main") { let _ = window.show(); let _ = window.set_focus(); } }
-                    "hide" => { if let Some(window) = app.get_webview_window("main") { let _ = window.hide(); } }
+                    "show_hide" => {
+                        if let Some(window) = app.get_webview_window("main") {
+                            if window.is_visible().unwrap_or(true) { let _ = window.hide(); } else { let _ = window.show(); let _ = window.set_focus(); }
+                            if let Some(item) = app.menu().and_then(|m| m.get("show_hide")) {
+                                if let Some(item) = item.as_menuitem() {
+                                    background::sync_tray_visibility_label(app, item);
+                                }
+                            }
+                        }
+                    }
                     _ => { println!("menu item {:?} not handled", event.id); }
                 })
                 .on_tray_icon_event(|tray, event| {
@@ -30,30 +65,56 @@ pub fn run() {
                     }
                 })
                 .build(app)?;
+
+            if let Some(window) = app.get_webview_window("main") {
+                let app_handle = app.handle().clone();
+                window.on_window_event(move |event| {
+                    if let WindowEvent::CloseRequested { api, .. } = event {
+                        if background::close_to_tray_enabled() {
+                            if let Some(window) = app_handle.get_webview_window("main") {
+                                let _ = window.hide();
+                            }
+                            if let Some(item) = app_handle.menu().and_then(|m| m.get("show_hide")) {
+                                if let Some(item) = item.as_menuitem() {
+                                    background::sync_tray_visibility_label(&app_handle, item);
+                                }
+                            }
+                            api.prevent_close();
+                        }
+                    }
+                });
+            }
             Ok(())
         })
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(logging::plugin())
         .invoke_handler(tauri::generate_handler![
             git::get_git_branches,
             git::get_git_commits,
             git::get_commit_files,
             git::get_commit_file_diff,
+            git::get_git_diff_structured,
+            git::get_commit_file_diff_structured,
             git::get_git_remotes,
             git::get_git_remote_branches,
             git::get_git_tags,
             git::get_git_stashes,
             git::git_checkout_remote_branch,
+            git::set_branch_upstream,
             git::switch_branch,
             git::get_ssh_key_info,
             git::generate_ssh_key,
+            git::generate_ssh_key_ex,
+            auth::add_key_to_agent,
             git::get_git_status,
             git::get_git_diff,
             git::get_avatar,
             git::clear_avatar_cache,
             git::git_commit,
             git::git_stage_file,
+            git::git_stage_hunk,
             git::git_stage_all,
             git::git_unstage_file,
             git::git_unstage_all,
@@ -66,7 +127,42 @@ pub fn run() {
             git::git_discard_changes,
             git::git_stash_save,
             git::git_stash_drop,
-            git::git_stash_pop
+            git::git_stash_pop,
+            branch_cleanup::git_classify_stale_branches,
+            branch_cleanup::git_trim_branches,
+            worktree::get_git_worktrees,
+            worktree::git_add_worktree,
+            worktree::git_remove_worktree,
+            worktree::git_prune_worktrees,
+            forge::get_forge_info,
+            forge::create_pull_request,
+            watcher::watch_repo,
+            watcher::unwatch_repo,
+            telemetry::set_telemetry_enabled,
+            telemetry::get_telemetry_enabled,
+            logging::open_log_directory,
+            background::set_close_to_tray,
+            background::get_close_to_tray,
+            background::set_launch_at_login,
+            background::get_launch_at_login,
+            vbranch::vbranch_list,
+            vbranch::vbranch_create,
+            vbranch::vbranch_assign_file,
+            vbranch::vbranch_commit,
+            vbranch::vbranch_path_owners,
+            credentials::store_remote_credentials,
+            credentials::forget_remote_credentials,
+            patch_email::git_format_patch,
+            patch_email::send_patch_email,
+            repo_registry::register_repo,
+            repo_registry::unregister_repo,
+            repo_registry::list_registered_repos,
+            repo_registry::batch_fetch,
+            repo_registry::batch_status,
+            auth::answer_credential_prompt,
+            auth::git_fetch_interactive,
+            auth::git_pull_interactive,
+            auth::git_push_interactive
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,53 @@
+//! Structured logging.
+//!
+//! Installs `tauri_plugin_log` so every command handler logs to both stdout
+//! and a rotating file under the app log dir. The app is quiet (`warn`) by
+//! default; building with the `debug` Cargo feature raises that to `debug`
+//! so users filing issues about a slow or failing git operation can grab a
+//! log with real detail instead of `println!`-ing to a scrollback nobody
+//! kept.
+//!
+//! Timing and exact-invocation logging for git itself isn't here - it lives
+//! in `git::run_git_command`, the single choke point nearly every git
+//! command goes through, so every caller gets it for free instead of having
+//! to hand-wrap each command individually.
+
+use tauri_plugin_log::{Target, TargetKind};
+
+/// The default level when the `debug` feature is not enabled.
+#[cfg(not(feature = "debug"))]
+const DEFAULT_LEVEL: log::LevelFilter = log::LevelFilter::Warn;
+
+/// The level used when the crate is built with `--features debug`.
+#[cfg(feature = "debug")]
+const DEFAULT_LEVEL: log::LevelFilter = log::LevelFilter::Debug;
+
+/// Builds the log plugin, logging to stdout and a rotating file.
+pub fn plugin<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
+    tauri_plugin_log::Builder::new()
+        .level(DEFAULT_LEVEL)
+        .targets([
+            Target::new(TargetKind::Stdout),
+            Target::new(TargetKind::LogDir { file_name: None }),
+        ])
+        .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepAll)
+        .max_file_size(5 * 1024 * 1024)
+        .build()
+}
+
+/// Opens the OS file manager at the directory containing Celerix's log
+/// files.
+///
+/// # Errors
+/// Returns an error if the app log directory cannot be resolved or the
+/// directory cannot be opened.
+#[tauri::command]
+pub fn open_log_directory(app_handle: tauri::AppHandle) -> Result<(), String> {
+    use tauri::Manager;
+    use tauri_plugin_opener::OpenerExt;
+    let log_dir = app_handle.path().app_log_dir().map_err(|e| e.to_string())?;
+    if !log_dir.exists() {
+        std::fs::create_dir_all(&log_dir).map_err(|e| e.to_string())?;
+    }
+    app_handle.opener().open_path(log_dir.to_string_lossy().to_string(), None::<String>).map_err(|e| e.to_string())
+}
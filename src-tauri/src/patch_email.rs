@@ -0,0 +1,137 @@
+//! Email-a-patch: `git format-patch` plus SMTP send.
+//!
+//! Lets users of mailing-list-driven projects contribute without leaving
+//! the GUI - generate an mbox series with `git format-patch --stdout` and
+//! hand it to an SMTP server, one message per commit, numbered and threaded
+//! like `git send-email` would.
+
+use serde::{Deserialize, Serialize};
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+use crate::git::run_git_command;
+
+/// SMTP connection details for sending a patch series.
+#[derive(Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: String,
+    pub password: String,
+}
+
+/// One commit's `format-patch` output, split into headers the frontend can
+/// prefill a compose form with.
+#[derive(Serialize)]
+pub struct FormattedPatch {
+    /// Commit subject, with any `[PATCH n/m]` prefix `format-patch` added.
+    pub subject: String,
+    /// `Name <email>` as it appears in the patch's `From:` header.
+    pub author: String,
+    /// The patch body, including the diff.
+    pub body: String,
+}
+
+/// Splits raw `format-patch --stdout` output (one or more mbox messages
+/// separated by `From <sha> ...` lines) into individual patches.
+fn split_mbox(mbox: &str) -> Vec<&str> {
+    let mut patches = Vec::new();
+    let mut start = 0;
+    let mut indices: Vec<usize> = mbox.match_indices("\nFrom ").map(|(i, _)| i + 1).collect();
+    indices.push(mbox.len());
+    for end in indices {
+        if end > start {
+            patches.push(mbox[start..end].trim_end());
+            start = end;
+        }
+    }
+    if patches.is_empty() && !mbox.trim().is_empty() {
+        patches.push(mbox.trim_end());
+    }
+    patches
+}
+
+/// Parses a single mbox-formatted patch's `Subject:`/`From:` headers and
+/// everything after the blank line as the body.
+fn parse_patch(raw: &str) -> FormattedPatch {
+    let mut subject = String::new();
+    let mut author = String::new();
+    let mut body_start = 0;
+    for (i, line) in raw.lines().enumerate() {
+        if line.is_empty() {
+            body_start = i + 1;
+            break;
+        }
+        if let Some(rest) = line.strip_prefix("Subject: ") {
+            subject = rest.to_string();
+        } else if let Some(rest) = line.strip_prefix("From: ") {
+            author = rest.to_string();
+        }
+    }
+    let body = raw.lines().skip(body_start).collect::<Vec<_>>().join("\n");
+    FormattedPatch { subject, author, body }
+}
+
+/// Runs `git format-patch --stdout` over `commit_range` and returns each
+/// commit as a separate `FormattedPatch`, numbered `[PATCH n/m]` by git
+/// itself when the range has more than one commit.
+///
+/// # Errors
+/// Returns an error if `format-patch` fails (e.g. an invalid range).
+#[tauri::command]
+pub async fn git_format_patch(path: String, commit_range: String) -> Result<Vec<FormattedPatch>, String> {
+    let output = run_git_command(&path, &["format-patch", "--stdout", &commit_range]).await?;
+    if !output.status.success() {
+        return Err(format!("git format-patch failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    let mbox = String::from_utf8_lossy(&output.stdout).to_string();
+    Ok(split_mbox(&mbox).into_iter().map(parse_patch).collect())
+}
+
+/// Sends a previously generated patch series by SMTP, one message per
+/// patch, threading replies via `In-Reply-To`/`References` so mail clients
+/// group the series.
+///
+/// # Errors
+/// Returns an error if any message fails to build or send.
+#[tauri::command]
+pub async fn send_patch_email(
+    smtp_config: SmtpConfig,
+    from: String,
+    recipients: Vec<String>,
+    mbox: String,
+) -> Result<(), String> {
+    let patches = split_mbox(&mbox).into_iter().map(parse_patch).collect::<Vec<_>>();
+    if patches.is_empty() {
+        return Err("No patches found in mbox".to_string());
+    }
+
+    let creds = Credentials::new(smtp_config.username, smtp_config.password);
+    let mailer = SmtpTransport::relay(&smtp_config.host)
+        .map_err(|e| e.to_string())?
+        .port(smtp_config.port)
+        .credentials(creds)
+        .build();
+
+    let mut previous_message_id: Option<String> = None;
+    for patch in &patches {
+        let mut builder = Message::builder()
+            .from(from.parse().map_err(|e: lettre::address::AddressError| e.to_string())?)
+            .subject(&patch.subject)
+            .header(ContentType::TEXT_PLAIN);
+        for recipient in &recipients {
+            builder = builder.to(recipient.parse().map_err(|e: lettre::address::AddressError| e.to_string())?);
+        }
+        if let Some(id) = &previous_message_id {
+            builder = builder.in_reply_to(id.clone()).references(id.clone());
+        }
+
+        let message = builder.body(patch.body.clone()).map_err(|e| e.to_string())?;
+        previous_message_id = message.headers().get_raw("Message-Id").map(|s| s.to_string());
+
+        mailer.send(&message).map_err(|e| format!("Failed to send '{}': {}", patch.subject, e))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Multi-repository registry with batch fetch/status.
+//!
+//! Every command elsewhere in this crate takes a single `path` - fine for
+//! working in one repo, but it means juggling several checkouts requires
+//! opening each one in turn just to see what needs attention. This keeps a
+//! small persisted list of repos the user has registered (with optional
+//! group tags, like a repo-manager config file) and runs `git_fetch`/an
+//! ahead-behind check across all of them concurrently, bounded by a
+//! semaphore so a large registry doesn't spawn unbounded git processes at
+//! once.
+
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::fs;
+use tokio::sync::Semaphore;
+
+use crate::git::run_git_command;
+
+/// How many repos to fetch/check concurrently.
+const MAX_CONCURRENT_BATCH_OPS: usize = 8;
+
+/// A single tracked repository.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RegisteredRepo {
+    /// Absolute path to the repository's working directory.
+    pub path: String,
+    /// Free-form tags the user grouped this repo under (e.g. "work", "oss").
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+/// On-disk shape of the registry's TOML config file.
+#[derive(Serialize, Deserialize, Default)]
+struct RepoRegistry {
+    #[serde(default, rename = "repo")]
+    repos: Vec<RegisteredRepo>,
+}
+
+/// Returns the path to the registry's config file. The containing
+/// directory is created by `save_registry`, not here.
+fn registry_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    Ok(app.path().app_config_dir().map_err(|e| e.to_string())?.join("repos.toml"))
+}
+
+/// Loads the registry, defaulting to empty if it doesn't exist yet.
+async fn load_registry(app: &AppHandle) -> Result<RepoRegistry, String> {
+    let path = registry_path(app)?;
+    if !path.exists() {
+        return Ok(RepoRegistry::default());
+    }
+    let contents = fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+    toml::from_str(&contents).map_err(|e| e.to_string())
+}
+
+/// Persists the registry.
+async fn save_registry(app: &AppHandle, registry: &RepoRegistry) -> Result<(), String> {
+    let path = registry_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+    }
+    let contents = toml::to_string_pretty(registry).map_err(|e| e.to_string())?;
+    fs::write(&path, contents).await.map_err(|e| e.to_string())
+}
+
+/// Adds `path` to the registry, updating its groups if it's already
+/// tracked.
+///
+/// # Errors
+/// Returns an error if the registry file can't be read or written.
+#[tauri::command]
+pub async fn register_repo(app: AppHandle, path: String, groups: Vec<String>) -> Result<(), String> {
+    let mut registry = load_registry(&app).await?;
+    match registry.repos.iter_mut().find(|r| r.path == path) {
+        Some(existing) => existing.groups = groups,
+        None => registry.repos.push(RegisteredRepo { path, groups }),
+    }
+    save_registry(&app, &registry).await
+}
+
+/// Removes `path` from the registry, if present.
+///
+/// # Errors
+/// Returns an error if the registry file can't be read or written.
+#[tauri::command]
+pub async fn unregister_repo(app: AppHandle, path: String) -> Result<(), String> {
+    let mut registry = load_registry(&app).await?;
+    registry.repos.retain(|r| r.path != path);
+    save_registry(&app, &registry).await
+}
+
+/// Returns every repo currently tracked in the registry.
+///
+/// # Errors
+/// Returns an error if the registry file exists but can't be read.
+#[tauri::command]
+pub async fn list_registered_repos(app: AppHandle) -> Result<Vec<RegisteredRepo>, String> {
+    Ok(load_registry(&app).await?.repos)
+}
+
+/// The outcome of fetching a single repo in a batch.
+#[derive(Serialize)]
+pub struct BatchFetchResult {
+    pub path: String,
+    pub error: Option<String>,
+}
+
+/// Fetches every repo in `paths` concurrently (bounded by a semaphore),
+/// collecting a per-repo result instead of aborting on the first failure.
+///
+/// # Errors
+/// Never fails itself; individual repo failures are reported in the
+/// returned vector's `error` fields.
+#[tauri::command]
+pub async fn batch_fetch(paths: Vec<String>) -> Result<Vec<BatchFetchResult>, String> {
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_BATCH_OPS));
+    let handles = paths.into_iter().map(|path| {
+        let semaphore = semaphore.clone();
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            let error = crate::git::git_fetch(path.clone()).await.err().map(|e| e.to_string());
+            BatchFetchResult { path, error }
+        })
+    }).collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for handle in handles {
+        results.push(handle.await.map_err(|e| e.to_string())?);
+    }
+    Ok(results)
+}
+
+/// How far a repo's current branch is ahead/behind its upstream.
+#[derive(Serialize)]
+pub struct RepoAheadBehind {
+    pub ahead: u32,
+    pub behind: u32,
+}
+
+/// The outcome of checking a single repo's status in a batch.
+#[derive(Serialize)]
+pub struct BatchStatusResult {
+    pub path: String,
+    pub status: Option<RepoAheadBehind>,
+    pub error: Option<String>,
+}
+
+/// Counts commits the current branch is ahead/behind its upstream via
+/// `git rev-list --left-right --count`.
+async fn ahead_behind(path: &str) -> Result<RepoAheadBehind, String> {
+    let output = run_git_command(path, &["rev-list", "--left-right", "--count", "HEAD...@{upstream}"]).await?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut counts = stdout.split_whitespace();
+    let ahead = counts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+    let behind = counts.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+    Ok(RepoAheadBehind { ahead, behind })
+}
+
+/// Computes ahead/behind status for every repo in `paths` concurrently
+/// (bounded by a semaphore), collecting a per-repo result instead of
+/// aborting on the first failure (e.g. a branch with no upstream set).
+///
+/// # Errors
+/// Never fails itself; individual repo failures are reported in the
+/// returned vector's `error` fields.
+#[tauri::command]
+pub async fn batch_status(paths: Vec<String>) -> Result<Vec<BatchStatusResult>, String> {
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_BATCH_OPS));
+    let handles = paths.into_iter().map(|path| {
+        let semaphore = semaphore.clone();
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            match ahead_behind(&path).await {
+                Ok(status) => BatchStatusResult { path, status: Some(status), error: None },
+                Err(error) => BatchStatusResult { path, status: None, error: Some(error) },
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for handle in handles {
+        results.push(handle.await.map_err(|e| e.to_string())?);
+    }
+    Ok(results)
+}
@@ -0,0 +1,258 @@
+//! Virtual branches: group uncommitted changes into named lanes and commit
+//! each lane independently, GitButler-style.
+//!
+//! A lane ("virtual branch") owns a subset of the currently changed paths.
+//! Committing a lane builds a tree containing only that lane's paths on top
+//! of the lane branch's own tip, commits it onto that branch, and restores
+//! the index exactly as it was, without touching the working tree - so the other lanes' changes are
+//! left untouched. Lane assignments are persisted under `.git/celerix` so
+//! they survive app restarts.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tokio::fs;
+
+use crate::git::run_git_command;
+
+/// Serializes access to a repo's `.git/index` across concurrent
+/// `vbranch_commit` calls, keyed by repo path.
+///
+/// Tauri dispatches async commands concurrently, so committing two
+/// different lanes back-to-back can otherwise interleave one call's
+/// `reset`/`add`/`restore` steps with another's, producing a lane commit
+/// with the wrong file set or leaving the index in a stale state.
+#[derive(Default)]
+pub struct IndexLocks(Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>);
+
+impl IndexLocks {
+    /// Returns the per-repo lock for `repo_path`, creating it on first use.
+    fn lock_for(&self, repo_path: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        locks.entry(repo_path.to_string()).or_insert_with(|| Arc::new(tokio::sync::Mutex::new(()))).clone()
+    }
+}
+
+/// A named lane that owns a subset of the working tree's changed paths.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VirtualBranch {
+    /// Stable identifier for the lane.
+    pub id: String,
+    /// User-facing lane name.
+    pub name: String,
+    /// The real branch this lane commits onto.
+    pub branch: String,
+    /// Paths (relative to the repo root) currently assigned to this lane.
+    pub paths: Vec<String>,
+}
+
+/// On-disk shape of `.git/celerix/vbranches.json`.
+#[derive(Serialize, Deserialize, Default)]
+struct VBranchStore {
+    lanes: Vec<VirtualBranch>,
+}
+
+/// Returns the path to the lane-assignment file for `repo_path`.
+fn store_path(repo_path: &str) -> Result<PathBuf, String> {
+    let repo = gix::open(repo_path).or_else(|_| gix::discover(repo_path)).map_err(|e| e.to_string())?;
+    Ok(repo.git_dir().join("celerix").join("vbranches.json"))
+}
+
+/// Loads the lane store, defaulting to empty if it doesn't exist yet.
+async fn load_store(repo_path: &str) -> Result<VBranchStore, String> {
+    let path = store_path(repo_path)?;
+    if !path.exists() {
+        return Ok(VBranchStore::default());
+    }
+    let contents = fs::read_to_string(&path).await.map_err(|e| e.to_string())?;
+    serde_json::from_str(&contents).map_err(|e| e.to_string())
+}
+
+/// Persists the lane store.
+async fn save_store(repo_path: &str, store: &VBranchStore) -> Result<(), String> {
+    let path = store_path(repo_path)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await.map_err(|e| e.to_string())?;
+    }
+    let contents = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    fs::write(&path, contents).await.map_err(|e| e.to_string())
+}
+
+/// Lists the virtual branches (lanes) defined for `repo_path`.
+///
+/// # Errors
+/// Returns an error if the lane store cannot be read.
+#[tauri::command]
+pub async fn vbranch_list(repo_path: String) -> Result<Vec<VirtualBranch>, String> {
+    Ok(load_store(&repo_path).await?.lanes)
+}
+
+/// Creates a new lane targeting a real branch of the same name.
+///
+/// # Errors
+/// Returns an error if a lane with that name already exists or the branch
+/// cannot be created.
+#[tauri::command]
+pub async fn vbranch_create(app: AppHandle, repo_path: String, name: String) -> Result<VirtualBranch, String> {
+    let mut store = load_store(&repo_path).await?;
+    if store.lanes.iter().any(|l| l.name == name) {
+        return Err(format!("Lane '{}' already exists", name));
+    }
+
+    let branch_exists = run_git_command(&repo_path, &["rev-parse", "--verify", "--quiet", &name]).await?.status.success();
+    if !branch_exists {
+        let output = run_git_command(&repo_path, &["branch", &name]).await?;
+        if !output.status.success() {
+            return Err(format!("Failed to create branch for lane: {}", String::from_utf8_lossy(&output.stderr)));
+        }
+    }
+
+    let lane = VirtualBranch { id: uuid::Uuid::new_v4().to_string(), name: name.clone(), branch: name, paths: Vec::new() };
+    store.lanes.push(lane.clone());
+    save_store(&repo_path, &store).await?;
+    let _ = app.emit("vbranch-changed", &repo_path);
+    Ok(lane)
+}
+
+/// Assigns `path` to `lane_id`, removing it from any other lane first so
+/// each changed path is owned by at most one lane.
+///
+/// # Errors
+/// Returns an error if the lane does not exist or the store cannot be
+/// saved.
+#[tauri::command]
+pub async fn vbranch_assign_file(app: AppHandle, repo_path: String, lane_id: String, path: String) -> Result<(), String> {
+    let mut store = load_store(&repo_path).await?;
+    for lane in store.lanes.iter_mut() {
+        lane.paths.retain(|p| p != &path);
+    }
+    let lane = store.lanes.iter_mut().find(|l| l.id == lane_id).ok_or_else(|| format!("Unknown lane '{}'", lane_id))?;
+    lane.paths.push(path);
+    save_store(&repo_path, &store).await?;
+    let _ = app.emit("vbranch-changed", &repo_path);
+    Ok(())
+}
+
+/// Commits a lane's assigned paths onto its branch, leaving the working
+/// tree, index, and every other lane's changes untouched.
+///
+/// Implementation: load the lane's own previous tip tree into the index
+/// (not `HEAD` - see below), `git add` only this lane's paths, write a
+/// tree from that index, commit it onto the lane's branch, then restore
+/// the index to what it was before we touched it. The whole sequence
+/// holds `IndexLocks`' per-repo lock, so a concurrent commit of a
+/// different lane waits instead of interleaving with ours on the shared
+/// index.
+///
+/// Starting from the lane's own tip tree rather than `HEAD` matters once a
+/// path is reassigned away from a lane after that lane has already
+/// committed it: starting from `HEAD` would silently revert that path back
+/// to its pre-lane-commit content on the *next* commit, even though the
+/// lane branch's own history says otherwise. Starting from the lane's tip
+/// keeps whatever it last committed for every path this commit doesn't
+/// touch.
+///
+/// # Errors
+/// Returns an error if the lane has no assigned paths, or if any of the
+/// underlying git plumbing commands fail.
+#[tauri::command]
+pub async fn vbranch_commit(
+    app: AppHandle,
+    locks: State<'_, IndexLocks>,
+    repo_path: String,
+    lane_id: String,
+    message: String,
+) -> Result<String, String> {
+    let lock = locks.lock_for(&repo_path);
+    let _guard = lock.lock().await;
+
+    let store = load_store(&repo_path).await?;
+    let lane = store.lanes.iter().find(|l| l.id == lane_id).ok_or_else(|| format!("Unknown lane '{}'", lane_id))?;
+    if lane.paths.is_empty() {
+        return Err("Lane has no assigned changes".to_string());
+    }
+
+    // Snapshot the current index into a tree we can restore once the lane's
+    // commit is built, so other lanes' staged/unstaged changes are untouched.
+    let backup_tree = run_git_command(&repo_path, &["write-tree"]).await?;
+    if !backup_tree.status.success() {
+        return Err(format!("Failed to snapshot index: {}", String::from_utf8_lossy(&backup_tree.stderr)));
+    }
+    let backup_tree_oid = String::from_utf8_lossy(&backup_tree.stdout).trim().to_string();
+
+    let lane_tip_tree = format!("{}^{{tree}}", lane.branch);
+    let reset = run_git_command(&repo_path, &["read-tree", &lane_tip_tree]).await?;
+    if !reset.status.success() {
+        return Err(format!("Failed to load lane tip tree into the index: {}", String::from_utf8_lossy(&reset.stderr)));
+    }
+
+    let mut add_args = vec!["add", "--"];
+    add_args.extend(lane.paths.iter().map(|p| p.as_str()));
+    let add = run_git_command(&repo_path, &add_args).await?;
+    if !add.status.success() {
+        restore_index(&repo_path, &backup_tree_oid).await?;
+        return Err(format!("Failed to stage lane paths: {}", String::from_utf8_lossy(&add.stderr)));
+    }
+
+    let lane_tree = run_git_command(&repo_path, &["write-tree"]).await?;
+    if !lane_tree.status.success() {
+        restore_index(&repo_path, &backup_tree_oid).await?;
+        return Err(format!("Failed to write lane tree: {}", String::from_utf8_lossy(&lane_tree.stderr)));
+    }
+    let lane_tree_oid = String::from_utf8_lossy(&lane_tree.stdout).trim().to_string();
+
+    let parent = run_git_command(&repo_path, &["rev-parse", &lane.branch]).await?;
+    if !parent.status.success() {
+        restore_index(&repo_path, &backup_tree_oid).await?;
+        return Err(format!("Failed to resolve lane branch '{}': {}", lane.branch, String::from_utf8_lossy(&parent.stderr)));
+    }
+    let parent_oid = String::from_utf8_lossy(&parent.stdout).trim().to_string();
+
+    let commit = run_git_command(&repo_path, &["commit-tree", &lane_tree_oid, "-p", &parent_oid, "-m", &message]).await?;
+    if !commit.status.success() {
+        restore_index(&repo_path, &backup_tree_oid).await?;
+        return Err(format!("Failed to create lane commit: {}", String::from_utf8_lossy(&commit.stderr)));
+    }
+    let commit_oid = String::from_utf8_lossy(&commit.stdout).trim().to_string();
+
+    let update_ref = run_git_command(&repo_path, &["update-ref", &format!("refs/heads/{}", lane.branch), &commit_oid]).await?;
+    if !update_ref.status.success() {
+        restore_index(&repo_path, &backup_tree_oid).await?;
+        return Err(format!("Failed to update lane branch ref: {}", String::from_utf8_lossy(&update_ref.stderr)));
+    }
+
+    restore_index(&repo_path, &backup_tree_oid).await?;
+
+    let _ = app.emit("vbranch-changed", &repo_path);
+    Ok(commit_oid)
+}
+
+/// Restores the index to the given tree oid without touching the working
+/// tree, so an uninvolved lane's pending changes stay staged exactly as
+/// they were.
+async fn restore_index(repo_path: &str, tree_oid: &str) -> Result<(), String> {
+    let output = run_git_command(repo_path, &["read-tree", tree_oid]).await?;
+    if !output.status.success() {
+        return Err(format!("Failed to restore index: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+/// Returns a map of changed path -> owning lane id, for the status view to
+/// annotate each file with its lane.
+///
+/// # Errors
+/// Returns an error if the lane store cannot be read.
+#[tauri::command]
+pub async fn vbranch_path_owners(repo_path: String) -> Result<HashMap<String, String>, String> {
+    let store = load_store(&repo_path).await?;
+    let mut owners = HashMap::new();
+    for lane in store.lanes {
+        for path in lane.paths {
+            owners.insert(path, lane.id.clone());
+        }
+    }
+    Ok(owners)
+}
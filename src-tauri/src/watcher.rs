@@ -0,0 +1,161 @@
+//! Live filesystem watching for repositories opened in the UI.
+//!
+//! Rather than forcing the frontend to re-poll `git::get_git_status` /
+//! `git::get_git_branches` on a timer, we watch the working tree and the
+//! `.git` directory with `notify` and push debounced Tauri events instead
+//! (mirroring GitButler's small `Event { name, payload }` model). Raw
+//! filesystem notifications are extremely noisy - index locks, editor swap
+//! files, `.git/*.lock` churn - so every incoming event resets a short timer
+//! per repo and only the settled state is ever emitted.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use notify::{Event as NotifyEvent, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// How long to wait for filesystem activity to settle before emitting.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Payload emitted alongside `repo://<repo-id>/status-changed` and
+/// `repo://<repo-id>/refs-changed`.
+#[derive(Serialize, Clone)]
+struct Event {
+    /// The repo-relative event name, e.g. `"status-changed"`.
+    name: String,
+    /// The absolute repository path that triggered the event.
+    payload: String,
+}
+
+/// Handle to a running watcher, kept alive only so it can be dropped
+/// (stopping the watch) when the repo is closed or re-watched.
+struct WatchHandle {
+    _watcher: RecommendedWatcher,
+}
+
+/// Tauri-managed state tracking one watcher per repo path.
+#[derive(Default)]
+pub struct WatcherState(Mutex<HashMap<String, WatchHandle>>);
+
+/// An id derived from the repo path, safe to embed in an event name.
+fn repo_id(path: &str) -> String {
+    format!("{:x}", md5::compute(path))
+}
+
+/// Returns true if `path` sits under `.git/refs` or is `.git/HEAD`.
+fn touches_refs(git_dir: &Path, path: &Path) -> bool {
+    if path == git_dir.join("HEAD") {
+        return true;
+    }
+    path.starts_with(git_dir.join("refs"))
+}
+
+/// Returns true for paths we never want to react to: lock files and
+/// anything `.gitignore`'d.
+fn is_noise(path: &Path, git_dir: &Path, ignore: &ignore::gitignore::Gitignore) -> bool {
+    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+        if name.ends_with(".lock") && path.starts_with(git_dir) {
+            return true;
+        }
+    }
+    ignore.matched(path, path.is_dir()).is_ignore()
+}
+
+/// Starts watching `path`'s working tree and `.git` directory, emitting
+/// `repo://<repo-id>/status-changed` and `repo://<repo-id>/refs-changed`
+/// events on the given app handle once changes settle.
+///
+/// Calling this again for the same path replaces the previous watcher.
+///
+/// # Errors
+/// Returns an error if the repository cannot be opened or the watcher
+/// cannot be installed.
+#[tauri::command]
+pub async fn watch_repo(app: AppHandle, path: String) -> Result<(), String> {
+    let repo = gix::open(&path).or_else(|_| gix::discover(&path)).map_err(|e| e.to_string())?;
+    let git_dir = repo.git_dir().to_path_buf();
+    let work_dir = repo.work_dir().map(|p| p.to_path_buf()).unwrap_or_else(|| PathBuf::from(&path));
+
+    let (builder, _) = ignore::gitignore::GitignoreBuilder::new(&work_dir).add(work_dir.join(".gitignore"));
+    let ignore = builder.build().map_err(|e| e.to_string())?;
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<NotifyEvent>();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res: notify::Result<NotifyEvent>| {
+        if let Ok(event) = res {
+            let _ = tx.send(event);
+        }
+    })
+    .map_err(|e| e.to_string())?;
+
+    watcher.watch(&work_dir, RecursiveMode::Recursive).map_err(|e| e.to_string())?;
+    if git_dir != work_dir {
+        watcher.watch(&git_dir, RecursiveMode::Recursive).map_err(|e| e.to_string())?;
+    }
+
+    {
+        let state = app.state::<WatcherState>();
+        let mut watchers = state.0.lock().map_err(|_| "Watcher state poisoned")?;
+        watchers.insert(path.clone(), WatchHandle { _watcher: watcher });
+    }
+
+    let id = repo_id(&path);
+    tauri::async_runtime::spawn(async move {
+        // Refs and status changes are tracked independently rather than
+        // collapsed into a single "most important kind" - a commit dirties
+        // both `.git/refs/heads/*` and the index/working tree in the same
+        // debounce window, and both panels need to refresh for that.
+        let mut pending_refs = false;
+        let mut pending_status = false;
+        let mut deadline = Instant::now() + DEBOUNCE;
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    let Some(event) = event else { break };
+                    if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)) {
+                        continue;
+                    }
+                    for changed in &event.paths {
+                        if is_noise(changed, &git_dir, &ignore) {
+                            continue;
+                        }
+                        if touches_refs(&git_dir, changed) {
+                            pending_refs = true;
+                        } else {
+                            pending_status = true;
+                        }
+                        deadline = Instant::now() + DEBOUNCE;
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline), if pending_refs || pending_status => {
+                    if pending_refs {
+                        let _ = app.emit(&format!("repo://{}/refs-changed", id), Event { name: "refs-changed".to_string(), payload: path.clone() });
+                    }
+                    if pending_status {
+                        let _ = app.emit(&format!("repo://{}/status-changed", id), Event { name: "status-changed".to_string(), payload: path.clone() });
+                    }
+                    pending_refs = false;
+                    pending_status = false;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Stops watching `path`, if a watcher is currently running for it.
+///
+/// # Errors
+/// Returns an error if the watcher state lock is poisoned.
+#[tauri::command]
+pub fn unwatch_repo(app: AppHandle, path: String) -> Result<(), String> {
+    let state = app.state::<WatcherState>();
+    let mut watchers = state.0.lock().map_err(|_| "Watcher state poisoned")?;
+    watchers.remove(&path);
+    Ok(())
+}
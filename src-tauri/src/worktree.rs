@@ -0,0 +1,118 @@
+//! Git worktree management.
+//!
+//! `switch_branch` checks out a branch in place, so only one branch can be
+//! on disk at a time. Worktrees let a second (or third) branch live in its
+//! own directory against the same repository - e.g. reviewing a PR branch
+//! while `main` keeps building in the original checkout - without stashing.
+
+use serde::Serialize;
+
+use crate::git::run_git_command;
+
+/// A single entry from `git worktree list --porcelain`.
+#[derive(Serialize)]
+pub struct GitWorktree {
+    /// Absolute path to the worktree's working directory.
+    pub path: String,
+    /// The branch checked out there, if any (detached worktrees have none).
+    pub branch: Option<String>,
+    /// The commit hash currently checked out.
+    pub head: String,
+    /// Whether this is the repository's bare worktree entry.
+    pub is_bare: bool,
+    /// Whether the worktree is locked against pruning/removal.
+    pub is_locked: bool,
+}
+
+/// Returns every worktree registered against this repository.
+///
+/// # Errors
+/// Returns an error if the Git worktree list command fails.
+#[tauri::command]
+pub async fn get_git_worktrees(path: String) -> Result<Vec<GitWorktree>, String> {
+    let output = run_git_command(&path, &["worktree", "list", "--porcelain"]).await?;
+    if !output.status.success() {
+        return Err(format!("Git worktree list failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut worktrees = Vec::new();
+    let mut current: Option<GitWorktree> = None;
+
+    for line in stdout.lines() {
+        if let Some(worktree_path) = line.strip_prefix("worktree ") {
+            if let Some(w) = current.take() { worktrees.push(w); }
+            current = Some(GitWorktree { path: worktree_path.to_string(), branch: None, head: String::new(), is_bare: false, is_locked: false });
+        } else if let Some(w) = current.as_mut() {
+            if let Some(head) = line.strip_prefix("HEAD ") {
+                w.head = head.to_string();
+            } else if let Some(branch_ref) = line.strip_prefix("branch ") {
+                w.branch = Some(branch_ref.strip_prefix("refs/heads/").unwrap_or(branch_ref).to_string());
+            } else if line == "bare" {
+                w.is_bare = true;
+            } else if line == "locked" || line.starts_with("locked ") {
+                w.is_locked = true;
+            }
+        }
+    }
+    if let Some(w) = current.take() { worktrees.push(w); }
+
+    Ok(worktrees)
+}
+
+/// Adds a new worktree at `new_path`.
+///
+/// If `create_branch` is `true`, `branch` names a new branch created from
+/// the current `HEAD` (`git worktree add -b <branch> <new_path>`).
+/// Otherwise `branch` is an existing branch or commit-ish to check out
+/// there (`git worktree add <new_path> <branch>`).
+///
+/// # Errors
+/// Returns an error if the worktree cannot be created, e.g. `branch` is
+/// already checked out elsewhere or `new_path` is not empty.
+#[tauri::command]
+pub async fn git_add_worktree(path: String, new_path: String, branch: String, create_branch: bool) -> Result<(), String> {
+    let output = if create_branch {
+        run_git_command(&path, &["worktree", "add", "-b", &branch, &new_path]).await?
+    } else {
+        run_git_command(&path, &["worktree", "add", &new_path, &branch]).await?
+    };
+    if !output.status.success() {
+        return Err(format!("Git worktree add failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+/// Removes a worktree.
+///
+/// # Arguments
+/// * `force` - Passes `--force`, removing it even with uncommitted changes.
+///
+/// # Errors
+/// Returns an error if the worktree is dirty and `force` is `false`, or
+/// the command otherwise fails.
+#[tauri::command]
+pub async fn git_remove_worktree(path: String, worktree_path: String, force: bool) -> Result<(), String> {
+    let mut args = vec!["worktree", "remove"];
+    if force { args.push("--force"); }
+    args.push(&worktree_path);
+    let output = run_git_command(&path, &args).await?;
+    if !output.status.success() {
+        return Err(format!("Git worktree remove failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+/// Prunes worktree administrative data for worktrees whose directories
+/// were deleted outside of Git.
+///
+/// # Errors
+/// Returns an error if the Git worktree prune command fails.
+#[tauri::command]
+pub async fn git_prune_worktrees(path: String) -> Result<(), String> {
+    let output = run_git_command(&path, &["worktree", "prune"]).await?;
+    if !output.status.success() {
+        return Err(format!("Git worktree prune failed: {}", String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}